@@ -0,0 +1,98 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use tui::widgets::ListState;
+use crate::app::SearchResultType;
+use crate::data::{Movie, Series, parse_release_date};
+use crate::i18n::Locale;
+
+/// Furthest ahead upcoming anniversaries are listed for, in days. Today and
+/// the days after it span less than a year, so no release comes round twice.
+pub const MAX_ANNIVERSARY_DAYS: u32 = 364;
+
+/// An episode or movie whose release date comes round again.
+#[derive(Debug, Clone)]
+pub struct Anniversary {
+    /// The day of the anniversary, today or in the next few days.
+    pub date: NaiveDate,
+    pub released: NaiveDate,
+    pub target: SearchResultType,
+    pub label: String,
+}
+
+impl Anniversary {
+    pub fn years_ago(&self) -> i32 {
+        self.date.year() - self.released.year()
+    }
+}
+
+/// State of the "On this day" panel.
+pub struct OnThisDay {
+    pub today: NaiveDate,
+    /// Anniversaries of today followed by the upcoming ones, by date.
+    pub anniversaries: Vec<Anniversary>,
+    pub list_state: ListState,
+}
+
+impl OnThisDay {
+    pub fn new(guide: &[Series], movies: &[Movie], today: NaiveDate, days_ahead: u32, region: &str, locale: Locale) -> Self {
+        let anniversaries = anniversaries(guide, movies, today, days_ahead, region, locale);
+        let mut list_state = ListState::default();
+        list_state.select(if anniversaries.is_empty() { None } else { Some(0) });
+        Self { today, anniversaries, list_state }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.anniversaries.is_empty() {
+            return;
+        }
+        let last = self.anniversaries.len() as isize - 1;
+        let selected = self.list_state.selected().unwrap_or(0) as isize;
+        self.list_state.select(Some((selected + delta).clamp(0, last) as usize));
+    }
+
+    pub fn selected(&self) -> Option<&Anniversary> {
+        self.anniversaries.get(self.list_state.selected()?)
+    }
+}
+
+/// Episodes and movies released on the month and day of `today`, or of one
+/// of the `days_ahead` days after it, in an earlier year. Release dates are
+/// the ones in `region`, or the original ones where it got no release. Releases on
+/// February 29 are remembered on February 28 in other years. Days beyond
+/// `MAX_ANNIVERSARY_DAYS` are left out.
+pub fn anniversaries(guide: &[Series], movies: &[Movie], today: NaiveDate, days_ahead: u32, region: &str, locale: Locale) -> Vec<Anniversary> {
+    let mut releases: Vec<(NaiveDate, SearchResultType, String)> = Vec::new();
+    for (series_index, series) in guide.iter().enumerate() {
+        for (episode_index, episode) in series.episodes.iter().enumerate() {
+            if let Some(date) = parse_release_date(episode.release_date_in(region)) {
+                let label = format!("{} {}: {}", series.series, episode.episode_number, episode.title);
+                releases.push((date, SearchResultType::Episode(series_index, episode_index), label));
+            }
+        }
+    }
+    for (movie_index, movie) in movies.iter().enumerate() {
+        if let Some(date) = parse_release_date(movie.release_date_in(region)) {
+            let label = locale.fill("Movie {}: {}", &[&movie.number, &movie.title]);
+            releases.push((date, SearchResultType::Movie(movie_index), label));
+        }
+    }
+
+    let mut anniversaries: Vec<Anniversary> = Vec::new();
+    for offset in 0..=days_ahead.min(MAX_ANNIVERSARY_DAYS) as i64 {
+        let day = match today.checked_add_signed(Duration::days(offset)) {
+            Some(day) => day,
+            None => break,
+        };
+        for (released, target, label) in &releases {
+            if released.year() < day.year() && anniversary_in(*released, day.year()) == Some(day) {
+                anniversaries.push(Anniversary { date: day, released: *released, target: target.clone(), label: label.clone() });
+            }
+        }
+    }
+    // Oldest release first within a day
+    anniversaries.sort_by_key(|anniversary| (anniversary.date, anniversary.released));
+    anniversaries
+}
+
+fn anniversary_in(released: NaiveDate, year: i32) -> Option<NaiveDate> {
+    released.with_year(year).or_else(|| NaiveDate::from_ymd_opt(year, 2, 28))
+}
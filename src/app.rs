@@ -1,6 +1,31 @@
+use std::time::{Duration, Instant};
 use tui::widgets::ListState;
-use chrono::NaiveDate; 
-use crate::data::{Series, Movie, load_guide_from_file, load_movies_from_file};
+use chrono::{Local, NaiveDate};
+use crate::anniversary::OnThisDay;
+use crate::config::{config_dir, load_config};
+use crate::data::{Series, Episode, Movie, Character, TitleLanguage, load_guide_from_file, load_movies_from_file, load_characters_from_file, parse_release_date, save_guide_to_file, save_movies_to_file};
+use crate::facets::{CHARACTER_FACETS, MOVIE_FACETS, FacetFilter, FacetKind, FilterPanel, FilterTarget};
+use crate::family::{first_selectable, relatives};
+use crate::goto::{GotoTarget, parse_goto};
+use crate::i18n::Locale;
+use crate::keymap::KeyMap;
+use crate::palette::Palette;
+use crate::quiz::{Quiz, generate_question};
+use crate::random::{RandomFilter, candidates, parse_filter, pick};
+use crate::stats::STATS_PAGES;
+use crate::theme::{Theme, load_user_themes};
+use crate::timeline::Timeline;
+use crate::user_data::{UserData, QuizScore, MAX_RATING, episode_key, movie_key, character_key, load_user_data_from_file, save_user_data_to_file, user_data_dir, user_data_path};
+
+/// How long a status bar message stays visible.
+const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(4);
+
+/// How many visited views going back can return to.
+const MAX_HISTORY: usize = 100;
+
+/// Titles of the main tabs, in `selected_tab` order.
+pub const TAB_TITLES: [&str; 6] = ["Episodes", "Movies", "Characters", "Favorites", "Stats", "Timeline"];
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SortOrder {
     Ascending,
@@ -12,6 +37,7 @@ pub enum EpisodeSortMethod {
     EpisodeNumber,
     Title,
     ReleaseDate,
+    Rating,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -19,34 +45,119 @@ pub enum MovieSortMethod {
     Number,
     Title,
     ReleaseDate,
+    Rating,
 }
 
 pub struct App {
     pub guide: Vec<Series>,
     pub movies: Vec<Movie>,
+    pub characters: Vec<Character>,
     pub list_state: ListState,
     pub app_mode: AppMode,
     pub selected_tab: usize,
     pub selected_series_tab: usize,
-    pub previous_tab: usize,
     pub search_query: String,
     pub search_results: Vec<SearchResult>,
-    pub previous_mode: AppMode,
+    pub history: Vec<View>,
+    pub forward_history: Vec<View>,
+    /// Order of the episodes of each series in the lists, as indices into
+    /// `guide`. Sorting changes only this, so that views keep pointing at
+    /// the same episodes.
+    pub episode_order: Vec<Vec<usize>>,
+    /// Order of the movies in the Movies tab, as indices into `movies`.
+    pub movie_order: Vec<usize>,
     pub episode_sort_method: EpisodeSortMethod,
     pub episode_sort_order: SortOrder,
     pub movie_sort_method: MovieSortMethod,
     pub movie_sort_order: SortOrder,
+    pub keymap: KeyMap,
+    pub pending_count: Option<usize>,
+    pub prompt: Option<Prompt>,
+    pub find_query: String,
+    pub list_height: usize,
+    pub themes: Vec<Theme>,
+    pub theme_index: usize,
+    pub split_min_width: u16,
+    pub wrap_around: bool,
+    pub user_data: UserData,
+    pub status_message: Option<(String, Instant)>,
+    pub palette: Option<Palette>,
+    /// Index into `STATS_PAGES` of the page shown in the Stats tab.
+    pub stats_page: usize,
+    pub stats_scroll: u16,
+    pub timeline: Timeline,
+    /// The "On this day" panel, while it is open.
+    pub on_this_day: Option<OnThisDay>,
+    pub anniversary_days: u32,
+    pub random_filter: RandomFilter,
+    /// The last random pick and the view it was opened in.
+    pub random_pick: Option<(SearchResultType, AppMode)>,
+    /// The running quiz session, in `AppMode::Quiz`.
+    pub quiz: Option<Quiz>,
+    /// Character picked first for a comparison, waiting for the second.
+    pub compare_with: Option<usize>,
+    /// Narrows the Characters tab; `list_state` selects among the
+    /// characters it lets through.
+    pub character_filter: FacetFilter,
+    /// Narrows the Movies tab the same way.
+    pub movie_filter: FacetFilter,
+    /// The filter panel, while it is open.
+    pub filter_panel: Option<FilterPanel>,
+    pub title_language: TitleLanguage,
+    /// Region code of the release dates shown, sorted by and put on the
+    /// timeline.
+    pub region: String,
+    /// Language of the interface text.
+    pub locale: Locale,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum AppMode {
     Characters,
+    CharacterDetails(usize),
+    /// Family tree of a character; `list_state` selects the relative.
+    FamilyTree(usize),
+    /// Two characters side by side.
+    CharacterComparison(usize, usize),
+    Favorites,
+    Stats,
+    Timeline,
     MoviesList,
     Details(usize, usize),
     EpisodesSeries(usize),
     MovieDetails(usize),
+    SagaDetails(usize, String),
     Search,
     Help,
+    Quiz,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptKind {
+    Find,
+    GoTo,
+    /// Editing the note of the item with this user data key.
+    Note(String),
+    /// Editing the rating of the item with this user data key.
+    Rating(String),
+    RandomFilter,
+}
+
+/// A single line of text being typed in the status bar.
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+}
+
+/// A visited view, with enough state to come back to it exactly.
+#[derive(Debug, Clone)]
+pub struct View {
+    pub mode: AppMode,
+    pub selected_tab: usize,
+    pub selected_series_tab: usize,
+    pub list_state: ListState,
+    pub search_query: String,
 }
 
 #[derive(Debug, Clone)]
@@ -55,54 +166,842 @@ pub struct SearchResult {
     pub title: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SearchResultType {
     Episode(usize, usize), // (series_index, episode_index)
     Movie(usize),          // movie_index
+    Character(usize),      // character_index
+}
+
+/// An entry of the Favorites tab.
+#[derive(Debug, Clone)]
+pub struct Favorite {
+    pub key: String,
+    pub target: SearchResultType,
+    pub title: String,
+    pub note: String,
 }
 
 impl App {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         let guide = load_guide_from_file("data/episodes.json")?;
         let movies = load_movies_from_file("data/movies.json")?;
+        let characters = load_characters_from_file("data/characters.json")?;
+        let config = load_config()?;
+        let user_data = match user_data_path() {
+            Some(path) => load_user_data_from_file(&path)?,
+            None => UserData::default(),
+        };
+        let keymap = KeyMap::new(config.keymap, &config.keys)?;
+        let locale = Locale::detect(config.locale.as_deref());
+        let random_filter = parse_filter(&config.random_filter, &guide, locale)
+            .map_err(|e| locale.fill("Invalid random_filter: {}", &[&e]))?;
+
+        // NO_COLOR (https://no-color.org) forces plain rendering whatever
+        // theme is configured
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let mut themes = Theme::builtin();
+        if let Some(dir) = config_dir() {
+            for theme in load_user_themes(&dir.join("themes"))? {
+                match themes.iter_mut().find(|t| t.name == theme.name) {
+                    Some(existing) => *existing = theme,
+                    None => themes.push(theme),
+                }
+            }
+        }
+        let theme_index = match &config.theme {
+            Some(name) => themes.iter().position(|t| &t.name == name)
+                .ok_or_else(|| format!("Unknown theme: {}", name))?,
+            None => 0,
+        };
+        let (themes, theme_index) = if config.plain || no_color {
+            (vec![Theme::plain()], 0)
+        } else {
+            (themes, theme_index)
+        };
 
         let mut list_state = ListState::default();
         list_state.select(Some(0));
 
+        let today = Local::now().date_naive();
+        let on_this_day = Some(OnThisDay::new(&guide, &movies, today, config.anniversary_days, &config.region.to_lowercase(), locale))
+            .filter(|panel| config.on_this_day && !panel.anniversaries.is_empty());
+
+        let episode_order = guide.iter().map(|series| (0..series.episodes.len()).collect()).collect();
+        let movie_order = (0..movies.len()).collect();
+
         Ok(Self {
             guide,
             movies,
+            characters,
             list_state,
             app_mode: AppMode::EpisodesSeries(0),
             selected_tab: 0,
             selected_series_tab: 0,
-            previous_tab: 0,
             search_query: String::new(),
             search_results: Vec::new(),
-            previous_mode: AppMode::EpisodesSeries(0),
+            history: Vec::new(),
+            forward_history: Vec::new(),
+            episode_order,
+            movie_order,
             episode_sort_method: EpisodeSortMethod::EpisodeNumber,
             episode_sort_order: SortOrder::Ascending,
             movie_sort_method: MovieSortMethod::Number,
             movie_sort_order: SortOrder::Ascending,
+            keymap,
+            pending_count: None,
+            prompt: None,
+            find_query: String::new(),
+            list_height: 0,
+            themes,
+            theme_index,
+            split_min_width: config.split_min_width,
+            wrap_around: config.wrap_around,
+            user_data,
+            status_message: None,
+            palette: None,
+            stats_page: 0,
+            stats_scroll: 0,
+            timeline: Timeline::default(),
+            on_this_day,
+            anniversary_days: config.anniversary_days,
+            random_filter,
+            random_pick: None,
+            quiz: None,
+            compare_with: None,
+            character_filter: FacetFilter::default(),
+            movie_filter: FacetFilter::default(),
+            filter_panel: None,
+            title_language: config.title_language,
+            region: config.region.to_lowercase(),
+            locale,
         })
     }
 
-    pub fn reset_list_state_for_tab(&mut self) {
-        match self.selected_tab {
-            0 => {
-                if self.selected_series_tab < self.guide.len() {
-                    self.list_state.select(Some(0));
+    /// Shows a transient message in the status bar.
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = Some((message.into(), Instant::now()));
+    }
+
+    pub fn current_status(&self) -> Option<&str> {
+        match &self.status_message {
+            Some((message, shown_at)) if shown_at.elapsed() < STATUS_MESSAGE_DURATION => Some(message),
+            _ => None,
+        }
+    }
+
+    pub fn save_user_data(&self) -> Result<(), Box<dyn std::error::Error>> {
+        match user_data_path() {
+            Some(path) => save_user_data_to_file(&self.user_data, &path),
+            None => Err(From::from("No data directory available to save user data")),
+        }
+    }
+
+    /// User data key and title of the episode, movie or character that is
+    /// selected in a list or open in a details view.
+    pub fn selected_item(&self) -> Option<(String, String)> {
+        let episode = |series_index: usize, episode_index: usize| {
+            let series = self.guide.get(series_index)?;
+            let episode = series.episodes.get(episode_index)?;
+            Some((episode_key(&series.series, episode.episode_number), episode.display_title(self.title_language).to_string()))
+        };
+        let movie = |movie_index: usize| {
+            self.movies.get(movie_index).map(|movie| (movie_key(movie.number), movie.display_title(self.title_language).to_string()))
+        };
+        let character = |character_index: usize| {
+            self.characters.get(character_index)
+                .map(|character| (character_key(&character.name), character.name.clone()))
+        };
+        match self.app_mode {
+            AppMode::EpisodesSeries(series_index) => episode(series_index, self.selected_episode(series_index)?),
+            AppMode::Details(series_index, episode_index) => episode(series_index, episode_index),
+            AppMode::MoviesList => movie(self.selected_movie()?),
+            AppMode::MovieDetails(movie_index) => movie(movie_index),
+            AppMode::Characters => character(self.selected_character()?),
+            AppMode::CharacterDetails(character_index) | AppMode::FamilyTree(character_index) => character(character_index),
+            AppMode::Favorites => {
+                let favorite = self.favorites().into_iter().nth(self.list_state.selected()?)?;
+                Some((favorite.key, favorite.title))
+            }
+            _ => None,
+        }
+    }
+
+    /// Like `selected_item`, but only for episodes and movies, which can be
+    /// watched and rated.
+    pub fn selected_episode_or_movie(&self) -> Option<(String, String)> {
+        self.selected_item().filter(|(key, _)| !key.starts_with(&character_key("")))
+    }
+
+    pub fn toggle_watched(&mut self) {
+        let (key, title) = match self.selected_episode_or_movie() {
+            Some(item) => item,
+            None => return,
+        };
+        let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+        let watched = self.user_data.toggle_watched(key, &today);
+        match self.save_user_data() {
+            Ok(()) if watched => self.set_status(self.locale.fill("Marked \"{}\" as watched", &[&title])),
+            Ok(()) => self.set_status(self.locale.fill("Marked \"{}\" as not watched", &[&title])),
+            Err(e) => self.set_status(self.locale.fill("Could not save watched state: {}", &[&e])),
+        }
+    }
+
+    pub fn toggle_favorite(&mut self) {
+        let (key, title) = match self.selected_item() {
+            Some(item) => item,
+            None => return,
+        };
+        let favorite = self.user_data.toggle_favorite(key);
+        match self.save_user_data() {
+            Ok(()) if favorite => self.set_status(self.locale.fill("Added \"{}\" to favorites", &[&title])),
+            Ok(()) => self.set_status(self.locale.fill("Removed \"{}\" from favorites", &[&title])),
+            Err(e) => self.set_status(self.locale.fill("Could not save favorites: {}", &[&e])),
+        }
+        // Unstarring in the Favorites tab removes the selected row
+        self.move_selection(0);
+    }
+
+    pub fn set_note(&mut self, key: String, note: &str) {
+        self.user_data.set_note(key, note);
+        match self.save_user_data() {
+            Ok(()) if note.trim().is_empty() => self.set_status(self.locale.tr("Removed note")),
+            Ok(()) => self.set_status(self.locale.tr("Saved note")),
+            Err(e) => self.set_status(self.locale.fill("Could not save note: {}", &[&e])),
+        }
+    }
+
+    /// Sets the rating of an item from what was typed in the rating
+    /// prompt; an empty input clears it.
+    pub fn set_rating(&mut self, key: String, input: &str) {
+        let rating = match input.trim() {
+            "" => None,
+            input => match input.parse::<u8>() {
+                Ok(rating) if (1..=MAX_RATING).contains(&rating) => Some(rating),
+                _ => return self.set_status(self.locale.fill("Ratings go from 1 to {}", &[&MAX_RATING])),
+            },
+        };
+        self.user_data.set_rating(key, rating);
+        // The rated item moves when the list is sorted by rating
+        if self.episode_sort_method == EpisodeSortMethod::Rating {
+            self.sort_episodes();
+        }
+        if self.movie_sort_method == MovieSortMethod::Rating {
+            self.sort_movies();
+        }
+        match self.save_user_data() {
+            Ok(()) => match rating {
+                Some(rating) => self.set_status(self.locale.fill("Rated {}/{}", &[&rating, &MAX_RATING])),
+                None => self.set_status(self.locale.tr("Removed rating")),
+            },
+            Err(e) => self.set_status(self.locale.fill("Could not save rating: {}", &[&e])),
+        }
+    }
+
+    /// The starred items that exist in the loaded data: episodes in guide
+    /// order, then movies, then characters.
+    pub fn favorites(&self) -> Vec<Favorite> {
+        let mut favorites: Vec<(Favorite, u32)> = self.user_data.favorites.iter()
+            .filter_map(|key| {
+                let (target, title, number) = self.resolve_key(key)?;
+                let note = self.user_data.note(key).unwrap_or_default().to_string();
+                Some((Favorite { key: key.clone(), target, title, note }, number))
+            })
+            .collect();
+        favorites.sort_by_key(|(favorite, number)| match favorite.target {
+            SearchResultType::Episode(series_index, _) => (0, series_index, *number),
+            SearchResultType::Movie(_) => (1, 0, *number),
+            SearchResultType::Character(character_index) => (2, character_index, 0),
+        });
+        favorites.into_iter().map(|(favorite, _)| favorite).collect()
+    }
+
+    /// Finds the item a user data key refers to, with its title and episode
+    /// or movie number.
+    fn resolve_key(&self, key: &str) -> Option<(SearchResultType, String, u32)> {
+        if let Some(number) = key.strip_prefix("movie/") {
+            let number: u32 = number.parse().ok()?;
+            let movie_index = self.movies.iter().position(|movie| movie.number == number)?;
+            let title = self.movies[movie_index].display_title(self.title_language).to_string();
+            return Some((SearchResultType::Movie(movie_index), title, number));
+        }
+        if let Some(name) = key.strip_prefix("character/") {
+            let character_index = self.characters.iter().position(|character| character.name == name)?;
+            return Some((SearchResultType::Character(character_index), name.to_string(), 0));
+        }
+        let (series_name, number) = key.rsplit_once('/')?;
+        let number: u32 = number.parse().ok()?;
+        let series_index = self.guide.iter().position(|series| series.series == series_name)?;
+        let series = &self.guide[series_index];
+        let episode_index = series.episodes.iter().position(|ep| ep.episode_number == number)?;
+        let title = format!("{} - {}", series.series, series.episodes[episode_index].display_title(self.title_language));
+        Some((SearchResultType::Episode(series_index, episode_index), title, number))
+    }
+
+    /// Opens the details view of a search result or favorite, switching to
+    /// the tab it belongs to.
+    pub fn open_item(&mut self, target: SearchResultType) {
+        match target {
+            SearchResultType::Episode(series_index, episode_index) => {
+                self.navigate(AppMode::Details(series_index, episode_index));
+                self.selected_tab = 0;
+                self.selected_series_tab = series_index;
+            }
+            SearchResultType::Movie(movie_index) => {
+                self.navigate(AppMode::MovieDetails(movie_index));
+                self.selected_tab = 1;
+            }
+            SearchResultType::Character(character_index) => {
+                self.navigate(AppMode::CharacterDetails(character_index));
+                self.selected_tab = 2;
+            }
+        }
+    }
+
+    /// Opens a random episode or movie allowed by `random_filter`. Picking
+    /// again while the last pick is open replaces it, in the history too.
+    pub fn pick_random(&mut self) {
+        let candidates = candidates(&self.random_filter, &self.guide, &self.movies, &self.user_data);
+        let previous = match &self.random_pick {
+            Some((target, mode)) if *mode == self.app_mode => Some(target.clone()),
+            _ => None,
+        };
+        let target = match pick(&candidates, previous.as_ref()) {
+            Some(target) => target,
+            None => {
+                self.set_status(self.locale.tr("Nothing matches the random filters"));
+                return;
+            }
+        };
+        if previous.is_some() {
+            self.go_back();
+            self.forward_history.clear();
+        }
+        self.open_item(target.clone());
+        self.random_pick = Some((target, self.app_mode.clone()));
+        self.set_status(self.locale.fill("Picked 1 of {}", &[&candidates.len()]));
+    }
+
+    /// Replaces the random filters with the ones written in `input` and
+    /// picks with them.
+    pub fn set_random_filter(&mut self, input: &str) {
+        match parse_filter(input, &self.guide, self.locale) {
+            Ok(filter) => {
+                self.random_filter = filter;
+                self.random_pick = None;
+                self.pick_random();
+            }
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    /// Opens the family tree of a character with its first relative that
+    /// is a character selected.
+    pub fn open_family_tree(&mut self, character_index: usize) {
+        self.navigate(AppMode::FamilyTree(character_index));
+        self.selected_tab = 2;
+        self.list_state.select(first_selectable(&relatives(&self.characters, character_index)));
+    }
+
+    /// Indices of the characters the character filter lets through.
+    pub fn visible_characters(&self) -> Vec<usize> {
+        self.characters.iter().enumerate()
+            .filter(|(_, character)| self.character_filter.matches(*character))
+            .map(|(character_index, _)| character_index)
+            .collect()
+    }
+
+    /// The character selected in the Characters tab.
+    pub fn selected_character(&self) -> Option<usize> {
+        self.visible_characters().get(self.list_state.selected()?).copied()
+    }
+
+    /// The episodes of a series in list order.
+    pub fn sorted_episodes(&self, series_index: usize) -> &[usize] {
+        self.episode_order.get(series_index).map_or(&[], Vec::as_slice)
+    }
+
+    /// The episode selected in the episodes list of a series.
+    pub fn selected_episode(&self, series_index: usize) -> Option<usize> {
+        self.sorted_episodes(series_index).get(self.list_state.selected()?).copied()
+    }
+
+    /// Indices of the movies the movie filter lets through, in the current
+    /// sort order.
+    pub fn visible_movies(&self) -> Vec<usize> {
+        self.movie_order.iter()
+            .copied()
+            .filter(|&movie_index| self.movie_filter.matches(&self.movies[movie_index]))
+            .collect()
+    }
+
+    /// The movie selected in the Movies tab.
+    pub fn selected_movie(&self) -> Option<usize> {
+        self.visible_movies().get(self.list_state.selected()?).copied()
+    }
+
+    pub fn filter(&self, target: FilterTarget) -> &FacetFilter {
+        match target {
+            FilterTarget::Characters => &self.character_filter,
+            FilterTarget::Movies => &self.movie_filter,
+        }
+    }
+
+    /// Opens the filter panel of the Characters or Movies tab. From the
+    /// details of a movie, it lists the genres, director and keywords of
+    /// that movie to show the movies sharing one.
+    pub fn open_filter_panel(&mut self) {
+        self.filter_panel = match self.app_mode {
+            AppMode::Characters => Some(FilterPanel::new(FilterTarget::Characters, &self.characters, &CHARACTER_FACETS, &self.character_filter)),
+            AppMode::MoviesList => Some(FilterPanel::new(FilterTarget::Movies, &self.movies, &MOVIE_FACETS, &self.movie_filter)),
+            AppMode::MovieDetails(movie_index) => self.movies.get(movie_index)
+                .map(|movie| FilterPanel::for_item(FilterTarget::Movies, &self.movies, &MOVIE_FACETS, movie)),
+            _ => {
+                self.set_status(self.locale.tr("Filters narrow the Characters and Movies lists"));
+                None
+            }
+        };
+    }
+
+    /// Picks the option selected in the filter panel, or drops it if it
+    /// was picked.
+    pub fn toggle_filter_option(&mut self) {
+        let panel = match self.filter_panel.as_mut() {
+            Some(panel) => panel,
+            None => return,
+        };
+        let (kind, value) = match panel.selected_option() {
+            Some(option) => (option.kind, option.value.clone()),
+            None => return,
+        };
+        if panel.jump {
+            self.filter_panel = None;
+            self.show_movies_with(kind, &value);
+            return;
+        }
+        match panel.target {
+            FilterTarget::Characters => {
+                self.character_filter.toggle(kind, &value);
+                panel.update_counts(&self.characters, &self.character_filter);
+            }
+            FilterTarget::Movies => {
+                self.movie_filter.toggle(kind, &value);
+                panel.update_counts(&self.movies, &self.movie_filter);
+            }
+        }
+        self.select_first();
+    }
+
+    pub fn clear_filter(&mut self) {
+        let panel = match self.filter_panel.as_mut() {
+            Some(panel) if !panel.jump => panel,
+            _ => return,
+        };
+        match panel.target {
+            FilterTarget::Characters => {
+                self.character_filter = FacetFilter::default();
+                panel.update_counts(&self.characters, &self.character_filter);
+            }
+            FilterTarget::Movies => {
+                self.movie_filter = FacetFilter::default();
+                panel.update_counts(&self.movies, &self.movie_filter);
+            }
+        }
+        self.select_first();
+    }
+
+    /// Opens the Movies tab filtered down to the movies with `value`.
+    pub fn show_movies_with(&mut self, kind: FacetKind, value: &str) {
+        self.navigate(AppMode::MoviesList);
+        self.selected_tab = 1;
+        self.movie_filter = FacetFilter::only(kind, value);
+        self.select_first();
+    }
+
+    /// Picks a character to compare. The first pick is remembered; picking
+    /// another one opens the comparison, picking the same one again drops it.
+    pub fn compare_character(&mut self, character_index: usize) {
+        let name = match self.characters.get(character_index) {
+            Some(character) => character.name.clone(),
+            None => return,
+        };
+        match self.compare_with.take() {
+            None => {
+                self.compare_with = Some(character_index);
+                self.set_status(self.locale.fill("Comparing {}: pick another character", &[&name]));
+            }
+            Some(first) if first == character_index => self.set_status(self.locale.tr("Comparison cancelled")),
+            Some(first) => {
+                self.navigate(AppMode::CharacterComparison(first, character_index));
+                self.selected_tab = 2;
+                self.status_message = None;
+            }
+        }
+    }
+
+    /// Starts a new quiz session with a first question.
+    pub fn start_quiz(&mut self) {
+        match generate_question(&self.guide, &self.movies, &self.characters, self.locale) {
+            Some(question) => {
+                self.quiz = Some(Quiz::new(question));
+                self.navigate(AppMode::Quiz);
+            }
+            None => self.set_status(self.locale.tr("Not enough data to make up a quiz")),
+        }
+    }
+
+    pub fn answer_quiz(&mut self, choice: usize) {
+        if let Some(quiz) = self.quiz.as_mut() {
+            if quiz.given.is_none() {
+                let right = quiz.answer(choice);
+                self.set_status(self.locale.tr(if right { "Right!" } else { "Wrong!" }));
+            }
+        }
+    }
+
+    pub fn next_quiz_question(&mut self) {
+        if let Some(question) = generate_question(&self.guide, &self.movies, &self.characters, self.locale) {
+            if let Some(quiz) = self.quiz.as_mut() {
+                quiz.next_question(question);
+            }
+        }
+    }
+
+    /// Ends the quiz session, saving its score among the high scores.
+    pub fn finish_quiz(&mut self) {
+        let quiz = match self.quiz.take() {
+            Some(quiz) if quiz.answered > 0 => quiz,
+            _ => return,
+        };
+        let score = QuizScore {
+            correct: quiz.correct,
+            answered: quiz.answered,
+            date: Local::now().date_naive().format("%Y-%m-%d").to_string(),
+        };
+        let place = self.user_data.record_quiz_score(score);
+        let message = match place {
+            Some(0) if quiz.correct > 0 => self.locale.fill("New best quiz score: {} of {} right", &[&quiz.correct, &quiz.answered]),
+            _ => self.locale.fill("Quiz over: {} of {} right", &[&quiz.correct, &quiz.answered]),
+        };
+        match self.save_user_data() {
+            Ok(()) => self.set_status(message),
+            Err(e) => self.set_status(self.locale.fill("Could not save quiz score: {}", &[&e])),
+        }
+    }
+
+    /// Share of the episodes of a series marked as watched, in percent.
+    pub fn series_watched_percent(&self, series_index: usize) -> u16 {
+        let series = match self.guide.get(series_index) {
+            Some(series) if !series.episodes.is_empty() => series,
+            _ => return 0,
+        };
+        let watched = series.episodes.iter()
+            .filter(|ep| self.user_data.is_watched(&episode_key(&series.series, ep.episode_number)))
+            .count();
+        (watched * 100 / series.episodes.len()) as u16
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.themes[self.theme_index]
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.theme_index = (self.theme_index + 1) % self.themes.len();
+    }
+
+    pub fn set_theme(&mut self, theme_index: usize) {
+        if theme_index < self.themes.len() {
+            self.theme_index = theme_index;
+        }
+    }
+
+    /// Shows another page of the Stats tab, `delta` pages away, wrapping
+    /// around.
+    pub fn step_stats_page(&mut self, delta: isize) {
+        let pages = STATS_PAGES.len() as isize;
+        self.stats_page = (self.stats_page as isize + delta).rem_euclid(pages) as usize;
+        self.stats_scroll = 0;
+    }
+
+    /// Opens the episodes list of a series, or the saga view of one of its
+    /// sagas.
+    pub fn open_series(&mut self, series_index: usize, saga: Option<String>) {
+        if series_index >= self.guide.len() {
+            return;
+        }
+        let mode = match saga {
+            Some(saga) => AppMode::SagaDetails(series_index, saga),
+            None => AppMode::EpisodesSeries(series_index),
+        };
+        self.navigate(mode);
+        self.selected_tab = 0;
+        self.selected_series_tab = series_index;
+        self.reset_list_state_for_tab();
+    }
+
+    /// Selects the episode or movie a "go to" reference points at, switching
+    /// series or tab as needed.
+    pub fn go_to(&mut self, reference: &str) {
+        match parse_goto(reference, self) {
+            Ok(GotoTarget::Episode(series_index, number)) => {
+                if self.app_mode != AppMode::EpisodesSeries(series_index) {
+                    self.open_series(series_index, None);
                 }
+                self.select_number(number as usize);
             }
-            1 => {
-                if !self.movies.is_empty() {
-                    self.list_state.select(Some(0));
+            Ok(GotoTarget::Movie(number)) => {
+                let hidden = self.visible_movies().iter().all(|&movie_index| self.movies[movie_index].number != number);
+                if hidden && !self.movie_filter.is_empty() {
+                    self.movie_filter = FacetFilter::default();
+                    self.set_status(self.locale.tr("Cleared the movie filter"));
                 }
+                if self.app_mode != AppMode::MoviesList {
+                    self.navigate(AppMode::MoviesList);
+                    self.selected_tab = 1;
+                }
+                self.select_number(number as usize);
             }
-            _ => self.list_state.select(None),
+            Err(e) => self.set_status(e),
+        }
+    }
+
+    /// Opens the "On this day" panel, or says so when nothing has an
+    /// anniversary soon.
+    pub fn show_on_this_day(&mut self) {
+        let today = Local::now().date_naive();
+        let panel = OnThisDay::new(&self.guide, &self.movies, today, self.anniversary_days, &self.region, self.locale);
+        if panel.anniversaries.is_empty() {
+            self.set_status(self.locale.tr("No anniversaries today or in the coming days"));
+        } else {
+            self.on_this_day = Some(panel);
         }
     }
 
+    /// Writes the episodes and movies, in their current sort order, and the
+    /// favorites to the `export` folder of the user data directory.
+    pub fn export(&mut self) {
+        let result = user_data_dir()
+            .ok_or_else(|| From::from("No data directory available"))
+            .and_then(|dir| -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+                let dir = dir.join("export");
+                std::fs::create_dir_all(&dir)?;
+                let guide: Vec<Series> = self.guide.iter().enumerate()
+                    .map(|(series_index, series)| Series {
+                        series: series.series.clone(),
+                        episodes: self.sorted_episodes(series_index).iter().map(|&index| series.episodes[index].clone()).collect(),
+                    })
+                    .collect();
+                let movies: Vec<Movie> = self.movie_order.iter().map(|&index| self.movies[index].clone()).collect();
+                save_guide_to_file(&guide, &dir.join("episodes.json").to_string_lossy())?;
+                save_movies_to_file(&movies, &dir.join("movies.json").to_string_lossy())?;
+                let favorites: Vec<_> = self.favorites().into_iter()
+                    .map(|favorite| serde_json::json!({
+                        "key": favorite.key,
+                        "title": favorite.title,
+                        "note": favorite.note,
+                    }))
+                    .collect();
+                std::fs::write(dir.join("favorites.json"), serde_json::to_string_pretty(&favorites)?)?;
+                Ok(dir)
+            });
+        match result {
+            Ok(dir) => self.set_status(self.locale.fill("Exported to {}", &[&dir.display()])),
+            Err(e) => self.set_status(self.locale.fill("Export failed: {}", &[&e])),
+        }
+    }
+
+    fn current_view(&self) -> View {
+        View {
+            mode: self.app_mode.clone(),
+            selected_tab: self.selected_tab,
+            selected_series_tab: self.selected_series_tab,
+            list_state: self.list_state.clone(),
+            search_query: self.search_query.clone(),
+        }
+    }
+
+    fn restore_view(&mut self, view: View) {
+        self.app_mode = view.mode;
+        self.selected_tab = view.selected_tab;
+        self.selected_series_tab = view.selected_series_tab;
+        self.search_query = view.search_query;
+        if self.app_mode == AppMode::Search {
+            // Re-running the search starts over at the first result; the
+            // query is the same, so the result selected before still is
+            self.perform_search();
+        } else {
+            self.search_results.clear();
+        }
+        self.list_state = view.list_state;
+    }
+
+    /// Switches to `mode`, remembering the current view so that `go_back`
+    /// can return to it. Callers update the tab fields afterwards.
+    pub fn navigate(&mut self, mode: AppMode) {
+        if self.app_mode == mode {
+            return;
+        }
+        self.history.push(self.current_view());
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.forward_history.clear();
+        self.app_mode = mode;
+    }
+
+    /// Returns to the previously visited view. Returns `false` if there is
+    /// none.
+    pub fn go_back(&mut self) -> bool {
+        match self.history.pop() {
+            Some(view) => {
+                self.forward_history.push(self.current_view());
+                self.restore_view(view);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn go_forward(&mut self) -> bool {
+        match self.forward_history.pop() {
+            Some(view) => {
+                self.history.push(self.current_view());
+                self.restore_view(view);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The list mode belonging to the currently selected main tab.
+    pub fn tab_mode(&self) -> AppMode {
+        self.tab_mode_for(self.selected_tab)
+    }
+
+    pub fn tab_mode_for(&self, tab: usize) -> AppMode {
+        match tab {
+            0 => AppMode::EpisodesSeries(self.selected_series_tab),
+            1 => AppMode::MoviesList,
+            2 => AppMode::Characters,
+            3 => AppMode::Favorites,
+            4 => AppMode::Stats,
+            5 => AppMode::Timeline,
+            _ => self.app_mode.clone(),
+        }
+    }
+
+    pub fn reset_list_state_for_tab(&mut self) {
+        self.select_first();
+    }
+
+    /// Number of entries in the list shown for the current mode.
+    pub fn current_list_len(&self) -> usize {
+        match self.app_mode {
+            AppMode::Search => self.search_results.len(),
+            _ => match self.selected_tab {
+                0 => self.guide.get(self.selected_series_tab).map_or(0, |series| series.episodes.len()),
+                1 => self.visible_movies().len(),
+                2 => self.visible_characters().len(),
+                3 => self.favorites().len(),
+                _ => 0,
+            },
+        }
+    }
+
+    /// Moves the list selection by `delta` rows, stopping at either end.
+    /// With `wrap_around`, a move that starts at one end continues at the
+    /// other instead.
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self.current_list_len();
+        if len == 0 {
+            self.list_state.select(None);
+            return;
+        }
+        let last = len as isize - 1;
+        let selected = (self.list_state.selected().unwrap_or(0) as isize).min(last);
+        let target = if self.wrap_around && delta > 0 && selected == last {
+            0
+        } else if self.wrap_around && delta < 0 && selected == 0 {
+            last
+        } else {
+            (selected + delta).clamp(0, last)
+        };
+        self.list_state.select(Some(target as usize));
+    }
+
+    pub fn select_first(&mut self) {
+        let len = self.current_list_len();
+        self.list_state.select(if len == 0 { None } else { Some(0) });
+    }
+
+    pub fn select_last(&mut self) {
+        let len = self.current_list_len();
+        self.list_state.select(len.checked_sub(1));
+    }
+
+    /// Selects the episode or movie with the given number in the current
+    /// list, wherever the active sort has placed it.
+    pub fn select_number(&mut self, number: usize) {
+        let position = match self.selected_tab {
+            0 => self.guide.get(self.selected_series_tab).and_then(|series| {
+                self.sorted_episodes(self.selected_series_tab).iter()
+                    .position(|&episode_index| series.episodes[episode_index].episode_number as usize == number)
+            }),
+            1 => self.visible_movies().iter().position(|&movie_index| self.movies[movie_index].number as usize == number),
+            _ => None,
+        };
+        let len = self.current_list_len();
+        match position {
+            Some(index) => self.list_state.select(Some(index)),
+            None if len > 0 => self.list_state.select(Some(number.clamp(1, len) - 1)),
+            None => {}
+        }
+    }
+
+    /// Titles of the entries in the list shown for the current mode.
+    fn current_list_titles(&self) -> Vec<String> {
+        match self.app_mode {
+            AppMode::Search => self.search_results.iter().map(|r| r.title.clone()).collect(),
+            _ => match self.selected_tab {
+                0 => self.guide.get(self.selected_series_tab)
+                    .map(|series| {
+                        self.sorted_episodes(self.selected_series_tab).iter()
+                            .map(|&episode_index| series.episodes[episode_index].display_title(self.title_language).to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                1 => self.visible_movies().into_iter()
+                    .map(|movie_index| self.movies[movie_index].display_title(self.title_language).to_string())
+                    .collect(),
+                2 => self.visible_characters().into_iter().map(|character_index| self.characters[character_index].name.clone()).collect(),
+                3 => self.favorites().into_iter().map(|favorite| favorite.title).collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    /// Selects the next (or previous) entry whose title contains the last
+    /// find query, wrapping around the list.
+    pub fn find_in_list(&mut self, forward: bool) -> bool {
+        let query = self.find_query.to_lowercase();
+        if query.is_empty() {
+            return false;
+        }
+        let titles = self.current_list_titles();
+        let len = titles.len();
+        if len == 0 {
+            return false;
+        }
+        let start = self.list_state.selected().unwrap_or(0);
+        let found = (1..=len)
+            .map(|offset| if forward { (start + offset) % len } else { (start + len - offset % len) % len })
+            .find(|&index| titles[index].to_lowercase().contains(&query));
+        if let Some(index) = found {
+            self.list_state.select(Some(index));
+        }
+        found.is_some()
+    }
+
     pub fn perform_search(&mut self) {
         self.search_results.clear();
         let query = self.search_query.to_lowercase();
@@ -110,10 +1009,10 @@ impl App {
         // Search episodes
         for (series_index, series) in self.guide.iter().enumerate() {
             for (episode_index, episode) in series.episodes.iter().enumerate() {
-                if episode.title.to_lowercase().contains(&query) || episode.description.to_lowercase().contains(&query) {
+                if episode.title_matches(&query) || episode.description.to_lowercase().contains(&query) {
                     self.search_results.push(SearchResult {
                         result_type: SearchResultType::Episode(series_index, episode_index),
-                        title: format!("{} - {}", series.series, episode.title),
+                        title: format!("{} - {}", series.series, episode.display_title(self.title_language)),
                     });
                 }
             }
@@ -121,21 +1020,47 @@ impl App {
 
         // Search movies
         for (movie_index, movie) in self.movies.iter().enumerate() {
-            if movie.title.to_lowercase().contains(&query) || movie.description.to_lowercase().contains(&query) {
+            if movie.title_matches(&query) || movie.description.to_lowercase().contains(&query) {
                 self.search_results.push(SearchResult {
                     result_type: SearchResultType::Movie(movie_index),
-                    title: movie.title.clone(),
+                    title: movie.display_title(self.title_language).to_string(),
                 });
             }
         }
+
+        // Search characters
+        for (character_index, character) in self.characters.iter().enumerate() {
+            let matches = character.name.to_lowercase().contains(&query)
+                || character.aliases.iter().any(|alias| alias.to_lowercase().contains(&query))
+                || character.description.to_lowercase().contains(&query);
+            if matches {
+                self.search_results.push(SearchResult {
+                    result_type: SearchResultType::Character(character_index),
+                    title: character.name.clone(),
+                });
+            }
+        }
+
+        self.select_first();
     }
 
     
+    pub fn set_episode_sort_method(&mut self, method: EpisodeSortMethod) {
+        self.episode_sort_method = method;
+        self.sort_episodes();
+    }
+
+    pub fn set_movie_sort_method(&mut self, method: MovieSortMethod) {
+        self.movie_sort_method = method;
+        self.sort_movies();
+    }
+
     pub fn toggle_episode_sort_method(&mut self) {
         self.episode_sort_method = match self.episode_sort_method {
             EpisodeSortMethod::EpisodeNumber => EpisodeSortMethod::Title,
             EpisodeSortMethod::Title => EpisodeSortMethod::ReleaseDate,
-            EpisodeSortMethod::ReleaseDate => EpisodeSortMethod::EpisodeNumber,
+            EpisodeSortMethod::ReleaseDate => EpisodeSortMethod::Rating,
+            EpisodeSortMethod::Rating => EpisodeSortMethod::EpisodeNumber,
         };
         self.sort_episodes();
     }
@@ -152,7 +1077,8 @@ impl App {
         self.movie_sort_method = match self.movie_sort_method {
             MovieSortMethod::Number => MovieSortMethod::Title,
             MovieSortMethod::Title => MovieSortMethod::ReleaseDate,
-            MovieSortMethod::ReleaseDate => MovieSortMethod::Number,
+            MovieSortMethod::ReleaseDate => MovieSortMethod::Rating,
+            MovieSortMethod::Rating => MovieSortMethod::Number,
         };
         self.sort_movies();
     }
@@ -166,40 +1092,86 @@ impl App {
     }
 
     fn sort_episodes(&mut self) {
-        for series in &mut self.guide {
-            series.episodes.sort_by(|a, b| {
+        let selected = match self.app_mode {
+            AppMode::EpisodesSeries(series_index) => self.selected_episode(series_index).map(|episode_index| (series_index, episode_index)),
+            _ => None,
+        };
+        let mut episode_order = Vec::new();
+        for series in &self.guide {
+            let rating = |ep: &Episode| self.user_data.rating(&episode_key(&series.series, ep.episode_number));
+            let mut order: Vec<usize> = (0..series.episodes.len()).collect();
+            order.sort_by(|&a, &b| {
+                let (a, b) = (&series.episodes[a], &series.episodes[b]);
                 let cmp = match self.episode_sort_method {
                     EpisodeSortMethod::EpisodeNumber => a.episode_number.cmp(&b.episode_number),
-                    EpisodeSortMethod::Title => a.title.cmp(&b.title),
+                    EpisodeSortMethod::Title => a.display_title(self.title_language).cmp(b.display_title(self.title_language)),
                     EpisodeSortMethod::ReleaseDate => {
-                        let date_a = NaiveDate::parse_from_str(&a.release_date, "%B %d, %Y").unwrap_or_default();
-                        let date_b = NaiveDate::parse_from_str(&b.release_date, "%B %d, %Y").unwrap_or_default();
+                        let date_a = sort_date(a.release_date_in(&self.region));
+                        let date_b = sort_date(b.release_date_in(&self.region));
                         date_a.cmp(&date_b)
                     },
+                    EpisodeSortMethod::Rating => match compare_ratings(rating(a), rating(b)) {
+                        Some(cmp) => cmp,
+                        None => return rating(b).cmp(&rating(a)),
+                    },
                 };
                 match self.episode_sort_order {
                     SortOrder::Ascending => cmp,
                     SortOrder::Descending => cmp.reverse(),
                 }
             });
+            episode_order.push(order);
+        }
+        self.episode_order = episode_order;
+        // Keep the selected episode selected where the sort moved it
+        if let Some((series_index, episode_index)) = selected {
+            let row = self.sorted_episodes(series_index).iter().position(|&index| index == episode_index);
+            self.list_state.select(row);
         }
     }
 
     fn sort_movies(&mut self) {
-        self.movies.sort_by(|a, b| {
+        let selected = if self.app_mode == AppMode::MoviesList { self.selected_movie() } else { None };
+        let rating = |movie: &Movie| self.user_data.rating(&movie_key(movie.number));
+        let mut order: Vec<usize> = (0..self.movies.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.movies[a], &self.movies[b]);
             let cmp = match self.movie_sort_method {
                 MovieSortMethod::Number => a.number.cmp(&b.number),
-                MovieSortMethod::Title => a.title.cmp(&b.title),
+                MovieSortMethod::Title => a.display_title(self.title_language).cmp(b.display_title(self.title_language)),
                 MovieSortMethod::ReleaseDate => {
-                    let date_a = NaiveDate::parse_from_str(&a.release_date, "%B %d, %Y").unwrap_or_default();
-                    let date_b = NaiveDate::parse_from_str(&b.release_date, "%B %d, %Y").unwrap_or_default();
+                    let date_a = sort_date(a.release_date_in(&self.region));
+                    let date_b = sort_date(b.release_date_in(&self.region));
                     date_a.cmp(&date_b)
                 },
+                MovieSortMethod::Rating => match compare_ratings(rating(a), rating(b)) {
+                    Some(cmp) => cmp,
+                    None => return rating(b).cmp(&rating(a)),
+                },
             };
             match self.movie_sort_order {
                 SortOrder::Ascending => cmp,
                 SortOrder::Descending => cmp.reverse(),
             }
         });
+        self.movie_order = order;
+        if let Some(movie_index) = selected {
+            let row = self.visible_movies().iter().position(|&index| index == movie_index);
+            self.list_state.select(row);
+        }
     }
-}
\ No newline at end of file
+}
+
+/// Parses a release date for sorting; unreadable dates sort first.
+fn sort_date(date: &str) -> NaiveDate {
+    parse_release_date(date).unwrap_or_default()
+}
+
+/// Compares two ratings when both are set. Otherwise returns `None`, and
+/// callers put unrated items last whatever the sort order.
+fn compare_ratings(a: Option<u8>, b: Option<u8>) -> Option<std::cmp::Ordering> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.cmp(&b)),
+        _ => None,
+    }
+}
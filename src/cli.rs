@@ -0,0 +1,120 @@
+use chrono::Local;
+use crate::anniversary::{MAX_ANNIVERSARY_DAYS, anniversaries};
+use crate::app::SearchResultType;
+use crate::config::load_config;
+use crate::data::{load_guide_from_file, load_movies_from_file};
+use crate::i18n::Locale;
+use crate::random::{candidates, parse_filter, pick};
+use crate::user_data::{UserData, load_user_data_from_file, user_data_path};
+
+const USAGE: &str = "Usage: dragonball-tui [on-this-day [DAYS] | random [FILTERS...]]
+
+Without a command, starts the interface.
+
+Commands:
+  on-this-day [DAYS]  List episodes and movies released on today's date in
+                      earlier years, and those with an anniversary in the next
+                      DAYS days (anniversary_days from the config by default)
+  random [FILTERS...] Print a random episode or movie. Filters are words such
+                      as dbz, saga:frieza, 1989-1995, max:30m, unwatched and
+                      canon (random_filter from the config by default)";
+
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["on-this-day"] => on_this_day(None),
+        ["on-this-day", days] => on_this_day(Some(days)),
+        ["random", filters @ ..] => random(&filters.join(" ")),
+        ["help" | "--help" | "-h"] => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => {
+            eprintln!("Unknown command: {}\n\n{}", args.join(" "), USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn on_this_day(days: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let guide = load_guide_from_file("data/episodes.json")?;
+    let movies = load_movies_from_file("data/movies.json")?;
+    let config = load_config()?;
+    let locale = Locale::detect(config.locale.as_deref());
+    let days = match days {
+        Some(days) => {
+            let days: u32 = days.parse().map_err(|_| locale.fill("Not a number of days: {}", &[&days]))?;
+            if days > MAX_ANNIVERSARY_DAYS {
+                return Err(locale.fill("Days go up to {}", &[&MAX_ANNIVERSARY_DAYS]).into());
+            }
+            days
+        }
+        None => config.anniversary_days,
+    };
+    let today = Local::now().date_naive();
+    let anniversaries = anniversaries(&guide, &movies, today, days, &config.region.to_lowercase(), locale);
+
+    let (today_list, upcoming): (Vec<_>, Vec<_>) = anniversaries.iter().partition(|anniversary| anniversary.date == today);
+    println!("{}", locale.fill("On this day, {}:", &[&today.format("%B %-d")]));
+    if today_list.is_empty() {
+        println!("  {}", locale.tr("Nothing was released on this day."));
+    }
+    for anniversary in today_list {
+        let years = locale.fill("{} years ago", &[&format!("{:>3}", anniversary.years_ago())]);
+        println!("  {}  {} ({})", years, anniversary.label, anniversary.released.format("%Y"));
+    }
+    if days > 0 {
+        println!();
+        println!("{}", locale.fill("Coming up in the next {} days:", &[&days]));
+        if upcoming.is_empty() {
+            println!("  {}", locale.tr("No anniversaries."));
+        }
+        for anniversary in upcoming {
+            println!(
+                "  {}  {:>3} {}  {} ({})",
+                anniversary.date.format("%a %b %e"),
+                anniversary.years_ago(),
+                locale.tr("years"),
+                anniversary.label,
+                anniversary.released.format("%Y"),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn random(filters: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let guide = load_guide_from_file("data/episodes.json")?;
+    let movies = load_movies_from_file("data/movies.json")?;
+    let config = load_config()?;
+    let locale = Locale::detect(config.locale.as_deref());
+    let filters = if filters.is_empty() { config.random_filter.clone() } else { filters.to_string() };
+    let filter = parse_filter(&filters, &guide, locale)?;
+    let user_data = match user_data_path() {
+        Some(path) => load_user_data_from_file(&path)?,
+        None => UserData::default(),
+    };
+
+    let candidates = candidates(&filter, &guide, &movies, &user_data);
+    let region = config.region.to_lowercase();
+    match pick(&candidates, None) {
+        Some(SearchResultType::Episode(series_index, episode_index)) => {
+            let series = &guide[series_index];
+            let episode = &series.episodes[episode_index];
+            println!("{} {}: {}", series.series, episode.episode_number, episode.display_title(config.title_language));
+            println!("{}, {}, {}", episode.release_date_in(&region), episode.duration, episode.saga);
+            println!();
+            println!("{}", episode.description);
+        }
+        Some(SearchResultType::Movie(movie_index)) => {
+            let movie = &movies[movie_index];
+            println!("{}", locale.fill("Movie {}: {}", &[&movie.number, &movie.display_title(config.title_language)]));
+            println!("{}", locale.fill("{}, {}, directed by {}", &[&movie.release_date_in(&region), &movie.runtime, &movie.director]));
+            println!();
+            println!("{}", movie.description);
+        }
+        _ => return Err(locale.tr("Nothing matches the random filters").into()),
+    }
+    println!();
+    println!("{}", locale.fill("Picked 1 of {}", &[&candidates.len()]));
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::anniversary::MAX_ANNIVERSARY_DAYS;
+use crate::data::{ORIGINAL_REGION, TitleLanguage};
+use crate::keymap::{Action, KeymapPreset};
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub keymap: KeymapPreset,
+    pub theme: Option<String>,
+    pub plain: bool,
+    /// Terminal width from which lists are shown next to a live preview.
+    pub split_min_width: u16,
+    /// Moving past either end of a list continues at the other end.
+    pub wrap_around: bool,
+    /// Show the "On this day" panel at startup when there are anniversaries.
+    pub on_this_day: bool,
+    /// How many days after today upcoming anniversaries are listed for.
+    pub anniversary_days: u32,
+    /// Filters random picks start with, e.g. "dbz unwatched max:30m".
+    pub random_filter: String,
+    /// Which title of episodes and movies is shown in lists.
+    pub title_language: TitleLanguage,
+    /// Region whose release dates are shown, sorted by and put on the
+    /// timeline, e.g. "us"; "jp" for the original Japanese ones.
+    pub region: String,
+    /// Language of the interface, e.g. "es"; taken from the environment
+    /// when not set.
+    pub locale: Option<String>,
+    pub keys: HashMap<Action, Vec<String>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            keymap: KeymapPreset::default(),
+            theme: None,
+            plain: false,
+            split_min_width: 120,
+            wrap_around: false,
+            on_this_day: true,
+            anniversary_days: 7,
+            random_filter: String::new(),
+            title_language: TitleLanguage::default(),
+            region: ORIGINAL_REGION.to_string(),
+            locale: None,
+            keys: HashMap::new(),
+        }
+    }
+}
+
+/// Directory holding the user's configuration, e.g. `~/.config/dragonball-tui`.
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("dragonball-tui"))
+}
+
+pub fn load_config() -> Result<Config, Box<dyn std::error::Error>> {
+    match config_dir() {
+        Some(dir) => load_config_from_file(&dir.join("config.toml")),
+        None => Ok(Config::default()),
+    }
+}
+
+pub fn load_config_from_file(path: &Path) -> Result<Config, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let file_content = fs::read_to_string(path)?;
+    let config: Config = toml::from_str(&file_content)
+        .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+    if config.anniversary_days > MAX_ANNIVERSARY_DAYS {
+        return Err(format!("Invalid config file {}: anniversary_days goes up to {}", path.display(), MAX_ANNIVERSARY_DAYS).into());
+    }
+    Ok(config)
+}
@@ -1,7 +1,9 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::prelude::*;
 use std::path::Path;
+use crate::i18n::Locale;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Episode {
@@ -11,6 +13,21 @@ pub struct Episode {
     pub release_date: String,
     pub duration: String,
     pub saga: String,
+    /// Anime-only episode not adapted from the manga.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub filler: bool,
+    /// Original Japanese title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_japanese: Option<String>,
+    /// The Japanese title in Latin letters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_romaji: Option<String>,
+    /// Titles of other dubs and releases.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternate_titles: Vec<String>,
+    /// Releases outside Japan; `release_date` is the original one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub releases: Vec<Release>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,6 +47,224 @@ pub struct Movie {
     pub genres: Vec<String>,
     pub trivia: String,
     pub plot_keywords: Vec<String>,
+    /// Part of the main story rather than a side story.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub canon: bool,
+    /// Series whose era the movie takes place in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<String>,
+    /// First and last episode of `era` the movie takes place around.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub episodes: Option<(u32, u32)>,
+    /// Original Japanese title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_japanese: Option<String>,
+    /// The Japanese title in Latin letters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_romaji: Option<String>,
+    /// Titles of other dubs and releases.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternate_titles: Vec<String>,
+    /// Releases outside Japan; `release_date` is the original one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub releases: Vec<Release>,
+}
+
+/// Region code of `release_date`, the original Japanese release.
+pub const ORIGINAL_REGION: &str = "jp";
+
+/// A release outside Japan, usually of a dub.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Release {
+    /// Region code, e.g. "us".
+    pub region: String,
+    pub date: String,
+    /// Studio behind the dub released there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dub: Option<String>,
+}
+
+/// Which title of episodes and movies lists show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleLanguage {
+    #[default]
+    English,
+    Romaji,
+    Japanese,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Character {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub series: Vec<String>,
+    pub race: String,
+    pub powers: Vec<String>,
+    pub description: String,
+    pub occupation: String,
+    pub family: Vec<String>,
+    pub key_events: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CharactersFile {
+    characters: Vec<Character>,
+}
+
+impl Series {
+    /// Names of the sagas in this series, in airing order.
+    pub fn sagas(&self) -> Vec<&str> {
+        let mut sagas: Vec<(&str, u32)> = Vec::new();
+        for episode in &self.episodes {
+            match sagas.iter_mut().find(|(saga, _)| *saga == episode.saga) {
+                Some((_, first)) => *first = (*first).min(episode.episode_number),
+                None => sagas.push((&episode.saga, episode.episode_number)),
+            }
+        }
+        sagas.sort_by_key(|(_, first)| *first);
+        sagas.into_iter().map(|(saga, _)| saga).collect()
+    }
+}
+
+impl Episode {
+    /// The title in `language`, or the English one when there is none.
+    pub fn display_title(&self, language: TitleLanguage) -> &str {
+        pick_title(&self.title, &self.title_romaji, &self.title_japanese, language)
+    }
+
+    /// Whether any of the titles contains `query`, which is lowercase.
+    pub fn title_matches(&self, query: &str) -> bool {
+        titles_match(&self.title, &self.title_romaji, &self.title_japanese, &self.alternate_titles, query)
+    }
+
+    /// The release date in `region`, if it was released there.
+    pub fn regional_release_date(&self, region: &str) -> Option<&str> {
+        regional_release_date(&self.release_date, &self.releases, region)
+    }
+
+    /// The release date in `region`, or the original one when it was not
+    /// released there.
+    pub fn release_date_in(&self, region: &str) -> &str {
+        self.regional_release_date(region).unwrap_or(&self.release_date)
+    }
+}
+
+impl Movie {
+    pub fn display_title(&self, language: TitleLanguage) -> &str {
+        pick_title(&self.title, &self.title_romaji, &self.title_japanese, language)
+    }
+
+    pub fn title_matches(&self, query: &str) -> bool {
+        titles_match(&self.title, &self.title_romaji, &self.title_japanese, &self.alternate_titles, query)
+    }
+
+    pub fn regional_release_date(&self, region: &str) -> Option<&str> {
+        regional_release_date(&self.release_date, &self.releases, region)
+    }
+
+    pub fn release_date_in(&self, region: &str) -> &str {
+        self.regional_release_date(region).unwrap_or(&self.release_date)
+    }
+
+    /// Whether the movie takes place around episode `episode_number` of
+    /// `series`.
+    pub fn takes_place_around(&self, series: &Series, episode_number: u32) -> bool {
+        self.era.as_ref() == Some(&series.series)
+            && self.episodes.is_some_and(|(first, last)| (first..=last).contains(&episode_number))
+    }
+
+    /// Sagas of `series` the movie takes place around, in airing order.
+    pub fn sagas<'a>(&self, series: &'a Series) -> Vec<&'a str> {
+        series.sagas().into_iter()
+            .filter(|saga| {
+                series.episodes.iter()
+                    .any(|episode| episode.saga == *saga && self.takes_place_around(series, episode.episode_number))
+            })
+            .collect()
+    }
+}
+
+/// Indices of the movies that take place around episode `episode_number`
+/// of `series`, or around any episode of `saga` when there is none.
+pub fn related_movies(movies: &[Movie], series: &Series, saga: &str, episode_number: Option<u32>) -> Vec<usize> {
+    movies.iter().enumerate()
+        .filter(|(_, movie)| match episode_number {
+            Some(episode_number) => movie.takes_place_around(series, episode_number),
+            None => movie.sagas(series).contains(&saga),
+        })
+        .map(|(movie_index, _)| movie_index)
+        .collect()
+}
+
+/// The date of the release in `region` among the original one and
+/// `releases`.
+pub fn regional_release_date<'a>(original: &'a str, releases: &'a [Release], region: &str) -> Option<&'a str> {
+    if region == ORIGINAL_REGION {
+        return Some(original);
+    }
+    releases.iter().find(|release| release.region == region).map(|release| release.date.as_str())
+}
+
+/// Display name of a region code, e.g. "Japan" or "US".
+pub fn region_name(region: &str, locale: Locale) -> String {
+    if region == ORIGINAL_REGION {
+        locale.tr("Japan").to_string()
+    } else {
+        region.to_uppercase()
+    }
+}
+
+fn pick_title<'a>(english: &'a str, romaji: &'a Option<String>, japanese: &'a Option<String>, language: TitleLanguage) -> &'a str {
+    let title = match language {
+        TitleLanguage::English => None,
+        TitleLanguage::Romaji => romaji.as_deref(),
+        TitleLanguage::Japanese => japanese.as_deref(),
+    };
+    title.unwrap_or(english)
+}
+
+fn titles_match(english: &str, romaji: &Option<String>, japanese: &Option<String>, alternates: &[String], query: &str) -> bool {
+    let query = fold_romaji(query);
+    std::iter::once(english)
+        .chain(romaji.as_deref())
+        .chain(japanese.as_deref())
+        .chain(alternates.iter().map(String::as_str))
+        .any(|title| fold_romaji(&title.to_lowercase()).contains(&query))
+}
+
+/// Drops the long vowel marks of romaji, so that "boru" finds "Bōru".
+fn fold_romaji(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ā' | 'â' => 'a',
+            'ē' | 'ê' => 'e',
+            'ī' | 'î' => 'i',
+            'ō' | 'ô' => 'o',
+            'ū' | 'û' => 'u',
+            c => c,
+        })
+        .collect()
+}
+
+/// Parses dates as written in the data files, e.g. "March  5, 1986".
+pub fn parse_release_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%B %d, %Y").ok()
+}
+
+/// Parses durations such as "25m", "1h" or "1h 12m" into minutes.
+pub fn parse_duration_minutes(duration: &str) -> Option<u32> {
+    let mut minutes = 0;
+    for part in duration.split_whitespace() {
+        if let Some(hours) = part.strip_suffix('h') {
+            minutes += hours.parse::<u32>().ok()? * 60;
+        } else if let Some(mins) = part.strip_suffix('m') {
+            minutes += mins.parse::<u32>().ok()?;
+        } else {
+            return None;
+        }
+    }
+    Some(minutes)
 }
 
 pub fn load_guide_from_file(file_path: &str) -> Result<Vec<Series>, Box<dyn std::error::Error>> {
@@ -43,7 +278,6 @@ pub fn load_guide_from_file(file_path: &str) -> Result<Vec<Series>, Box<dyn std:
     Ok(guide)
 }
 
-#[allow(dead_code)]
 pub fn save_guide_to_file(guide: &Vec<Series>, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
     let path = Path::new(file_path);
     let file_content = serde_json::to_string_pretty(&guide)?;
@@ -63,11 +297,21 @@ pub fn load_movies_from_file(file_path: &str) -> Result<Vec<Movie>, Box<dyn std:
     Ok(movies)
 }
 
-#[allow(dead_code)]
 pub fn save_movies_to_file(movies: &Vec<Movie>, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
     let path = Path::new(file_path);
     let file_content = serde_json::to_string_pretty(&movies)?;
     let mut file = fs::File::create(path)?;
     file.write_all(file_content.as_bytes())?;
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn load_characters_from_file(file_path: &str) -> Result<Vec<Character>, Box<dyn std::error::Error>> {
+    let path = Path::new(file_path);
+    if !path.exists() {
+        return Err(From::from(format!("File not found: {}", file_path)));
+    }
+
+    let file_content = fs::read_to_string(path)?;
+    let file: CharactersFile = serde_json::from_str(&file_content)?;
+    Ok(file.characters)
+}
@@ -0,0 +1,220 @@
+use crate::data::{Character, Movie};
+
+/// A property lists can be narrowed down by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FacetKind {
+    Race,
+    Series,
+    Power,
+    Genre,
+    Director,
+    Keyword,
+}
+
+pub const CHARACTER_FACETS: [FacetKind; 3] = [FacetKind::Race, FacetKind::Series, FacetKind::Power];
+pub const MOVIE_FACETS: [FacetKind; 3] = [FacetKind::Genre, FacetKind::Director, FacetKind::Keyword];
+
+/// The list a filter panel narrows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterTarget {
+    Characters,
+    Movies,
+}
+
+impl FacetKind {
+    pub fn title(self) -> &'static str {
+        match self {
+            FacetKind::Race => "Race",
+            FacetKind::Series => "Series",
+            FacetKind::Power => "Powers",
+            FacetKind::Genre => "Genres",
+            FacetKind::Director => "Directors",
+            FacetKind::Keyword => "Plot Keywords",
+        }
+    }
+
+    /// Whether an item with `value` matches the option `option`. Powers
+    /// also match their forms, so that "Super Saiyan" takes in "Super
+    /// Saiyan Blue".
+    fn matches(self, value: &str, option: &str) -> bool {
+        match self {
+            FacetKind::Power => value == option || value.strip_prefix(option).is_some_and(|rest| rest.starts_with(' ')),
+            _ => value == option,
+        }
+    }
+}
+
+/// Something with values for some of the facet kinds.
+pub trait Faceted {
+    fn facet_values(&self, kind: FacetKind) -> Vec<&str>;
+}
+
+impl Faceted for Character {
+    fn facet_values(&self, kind: FacetKind) -> Vec<&str> {
+        match kind {
+            FacetKind::Race => vec![self.race.as_str()],
+            FacetKind::Series => self.series.iter().map(String::as_str).collect(),
+            FacetKind::Power => self.powers.iter().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Faceted for Movie {
+    fn facet_values(&self, kind: FacetKind) -> Vec<&str> {
+        match kind {
+            FacetKind::Genre => self.genres.iter().map(String::as_str).collect(),
+            FacetKind::Director => vec![self.director.as_str()],
+            FacetKind::Keyword => self.plot_keywords.iter().map(String::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The options picked in a filter panel. An item passes when it matches
+/// one of the picked options of every kind that has any.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FacetFilter {
+    pub picked: Vec<(FacetKind, String)>,
+}
+
+impl FacetFilter {
+    pub fn is_empty(&self) -> bool {
+        self.picked.is_empty()
+    }
+
+    pub fn is_picked(&self, kind: FacetKind, value: &str) -> bool {
+        self.picked.iter().any(|(picked_kind, picked)| *picked_kind == kind && picked == value)
+    }
+
+    pub fn toggle(&mut self, kind: FacetKind, value: &str) {
+        match self.picked.iter().position(|(picked_kind, picked)| *picked_kind == kind && picked == value) {
+            Some(position) => {
+                self.picked.remove(position);
+            }
+            None => self.picked.push((kind, value.to_string())),
+        }
+    }
+
+    pub fn matches(&self, item: &impl Faceted) -> bool {
+        self.matches_except(item, None)
+    }
+
+    /// Like `matches`, leaving out the options of `skip`.
+    fn matches_except(&self, item: &impl Faceted, skip: Option<FacetKind>) -> bool {
+        let mut kinds: Vec<FacetKind> = Vec::new();
+        for (kind, _) in &self.picked {
+            if Some(*kind) != skip && !kinds.contains(kind) {
+                kinds.push(*kind);
+            }
+        }
+        kinds.into_iter().all(|kind| {
+            let values = item.facet_values(kind);
+            self.picked.iter()
+                .filter(|(picked_kind, _)| *picked_kind == kind)
+                .any(|(_, option)| values.iter().any(|value| kind.matches(value, option)))
+        })
+    }
+
+    /// A filter with just one option picked.
+    pub fn only(kind: FacetKind, value: &str) -> Self {
+        Self { picked: vec![(kind, value.to_string())] }
+    }
+
+    /// The picked options, e.g. "Saiyan, Flight".
+    pub fn describe(&self) -> String {
+        self.picked.iter().map(|(_, value)| value.as_str()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// An option of the filter panel.
+#[derive(Debug, Clone)]
+pub struct FacetOption {
+    pub kind: FacetKind,
+    pub value: String,
+    /// Items that would pass with this option picked, given the options
+    /// picked for the other kinds.
+    pub count: usize,
+}
+
+/// State of the filter panel.
+pub struct FilterPanel {
+    pub target: FilterTarget,
+    pub kinds: Vec<FacetKind>,
+    /// Options grouped by kind, most common first within a kind.
+    pub options: Vec<FacetOption>,
+    pub selected: usize,
+    /// Set when the panel lists the values of one item: picking an option
+    /// then shows everything sharing it instead of narrowing the filter.
+    pub jump: bool,
+}
+
+impl FilterPanel {
+    pub fn new<T: Faceted>(target: FilterTarget, items: &[T], kinds: &[FacetKind], filter: &FacetFilter) -> Self {
+        let mut options: Vec<FacetOption> = Vec::new();
+        for &kind in kinds {
+            let mut values: Vec<(String, usize)> = Vec::new();
+            for item in items {
+                for value in item.facet_values(kind) {
+                    match values.iter_mut().find(|(known, _)| known == value) {
+                        Some((_, count)) => *count += 1,
+                        None => values.push((value.to_string(), 1)),
+                    }
+                }
+            }
+            values.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+            options.extend(values.into_iter().map(|(value, _)| FacetOption { kind, value, count: 0 }));
+        }
+        let mut panel = Self { target, kinds: kinds.to_vec(), options, selected: 0, jump: false };
+        panel.update_counts(items, filter);
+        panel
+    }
+
+    /// The panel listing only the values of `item`, each counted over all
+    /// items, to jump to the ones sharing it.
+    pub fn for_item<T: Faceted>(target: FilterTarget, items: &[T], kinds: &[FacetKind], item: &T) -> Self {
+        let mut panel = Self::new(target, items, kinds, &FacetFilter::default());
+        panel.options.retain(|option| item.facet_values(option.kind).contains(&option.value.as_str()));
+        panel.kinds.retain(|&kind| panel.options.iter().any(|option| option.kind == kind));
+        panel.jump = true;
+        panel
+    }
+
+    /// Counts again what every option would let through, after the picked
+    /// options changed.
+    pub fn update_counts<T: Faceted>(&mut self, items: &[T], filter: &FacetFilter) {
+        for option in &mut self.options {
+            option.count = items.iter()
+                .filter(|item| filter.matches_except(*item, Some(option.kind)))
+                .filter(|item| item.facet_values(option.kind).iter().any(|value| option.kind.matches(value, &option.value)))
+                .count();
+        }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.options.len() as isize - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, last.max(0)) as usize;
+    }
+
+    /// Moves the selection to the first option of the next (or previous)
+    /// kind.
+    pub fn jump_kind(&mut self, forward: bool) {
+        let kind = match self.selected_option() {
+            Some(option) => option.kind,
+            None => return,
+        };
+        let position = self.kinds.iter().position(|&other| other == kind).unwrap_or(0);
+        let target = if forward { position + 1 } else { position.saturating_sub(1) };
+        let target = match self.kinds.get(target) {
+            Some(&target) => target,
+            None => return,
+        };
+        if let Some(first) = self.options.iter().position(|option| option.kind == target) {
+            self.selected = first;
+        }
+    }
+
+    pub fn selected_option(&self) -> Option<&FacetOption> {
+        self.options.get(self.selected)
+    }
+}
@@ -0,0 +1,224 @@
+use crate::data::Character;
+
+/// How a relative is related to a character.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Relation {
+    Grandparent,
+    Parent,
+    Sibling,
+    Spouse,
+    Child,
+    Grandchild,
+    /// Ancestors, creators, incarnations and anything else that does not
+    /// fit a family tree.
+    Other,
+}
+
+/// A row of the family tree, oldest first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Generation {
+    Grandparents,
+    Parents,
+    Own,
+    Children,
+    Grandchildren,
+    Other,
+}
+
+pub const GENERATIONS: [Generation; 6] = [
+    Generation::Grandparents,
+    Generation::Parents,
+    Generation::Own,
+    Generation::Children,
+    Generation::Grandchildren,
+    Generation::Other,
+];
+
+/// One entry of a character's family.
+#[derive(Debug, Clone)]
+pub struct Relative {
+    pub name: String,
+    pub relation: Relation,
+    /// The relation as written in the data, e.g. "adopted son".
+    pub label: String,
+    /// Index into the characters when the relative is one of them.
+    pub character: Option<usize>,
+}
+
+impl Relation {
+    /// Reads relations such as "father", "wife" or "adopted son".
+    pub fn parse(label: &str) -> Relation {
+        let label = label.to_lowercase();
+        let last_word = label.split_whitespace().last().unwrap_or("");
+        match last_word {
+            "grandfather" | "grandmother" | "grandparent" => Relation::Grandparent,
+            "father" | "mother" | "parent" => Relation::Parent,
+            "brother" | "sister" | "sibling" => Relation::Sibling,
+            "wife" | "husband" | "spouse" => Relation::Spouse,
+            "son" | "daughter" | "child" => Relation::Child,
+            "grandson" | "granddaughter" | "grandchild" => Relation::Grandchild,
+            _ => Relation::Other,
+        }
+    }
+
+    /// The relation seen from the other side, if the family tree has one.
+    fn inverse(&self) -> Option<(Relation, &'static str)> {
+        match self {
+            Relation::Grandparent => Some((Relation::Grandchild, "grandchild")),
+            Relation::Parent => Some((Relation::Child, "child")),
+            Relation::Sibling => Some((Relation::Sibling, "sibling")),
+            Relation::Spouse => Some((Relation::Spouse, "spouse")),
+            Relation::Child => Some((Relation::Parent, "parent")),
+            Relation::Grandchild => Some((Relation::Grandparent, "grandparent")),
+            Relation::Other => None,
+        }
+    }
+
+    pub fn generation(&self) -> Generation {
+        match self {
+            Relation::Grandparent => Generation::Grandparents,
+            Relation::Parent => Generation::Parents,
+            Relation::Sibling | Relation::Spouse => Generation::Own,
+            Relation::Child => Generation::Children,
+            Relation::Grandchild => Generation::Grandchildren,
+            Relation::Other => Generation::Other,
+        }
+    }
+}
+
+impl Generation {
+    pub fn title(self) -> &'static str {
+        match self {
+            Generation::Grandparents => "Grandparents",
+            Generation::Parents => "Parents",
+            Generation::Own => "",
+            Generation::Children => "Children",
+            Generation::Grandchildren => "Grandchildren",
+            Generation::Other => "Other",
+        }
+    }
+}
+
+/// Splits a family entry such as "Bardock (father)" into the name and the
+/// relation. Entries without a relation, such as "Unknown", are skipped.
+pub fn parse_family_entry(entry: &str) -> Option<(String, String)> {
+    let (name, rest) = entry.split_once('(')?;
+    let label = rest.strip_suffix(')')?.trim();
+    let name = name.trim();
+    if name.is_empty() || label.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), label.to_string()))
+}
+
+/// Finds the character a family entry names, by name or alias.
+fn find_character(characters: &[Character], name: &str) -> Option<usize> {
+    characters.iter().position(|character| character.name == name)
+        .or_else(|| characters.iter().position(|character| character.aliases.iter().any(|alias| alias == name)))
+}
+
+/// The family of a character: the entries of its own `family`, followed by
+/// characters that list it as a relative without being listed back.
+pub fn relatives(characters: &[Character], character_index: usize) -> Vec<Relative> {
+    let character = match characters.get(character_index) {
+        Some(character) => character,
+        None => return Vec::new(),
+    };
+    let mut relatives: Vec<Relative> = character.family.iter()
+        .filter_map(|entry| parse_family_entry(entry))
+        .map(|(name, label)| Relative {
+            character: find_character(characters, &name).filter(|&index| index != character_index),
+            relation: Relation::parse(&label),
+            name,
+            label,
+        })
+        .collect();
+
+    for (other_index, other) in characters.iter().enumerate() {
+        if other_index == character_index || relatives.iter().any(|relative| relative.character == Some(other_index)) {
+            continue;
+        }
+        let inverse = other.family.iter()
+            .filter_map(|entry| parse_family_entry(entry))
+            .filter(|(name, _)| find_character(characters, name) == Some(character_index))
+            .find_map(|(_, label)| Relation::parse(&label).inverse());
+        if let Some((relation, label)) = inverse {
+            relatives.push(Relative { name: other.name.clone(), relation, label: label.to_string(), character: Some(other_index) });
+        }
+    }
+    relatives
+}
+
+/// The rows of the family tree with indices into `relatives`; `None` in
+/// the own generation stands for the character itself, between its
+/// siblings and its spouses. Empty rows other than the own are left out.
+pub fn tree_rows(relatives: &[Relative]) -> Vec<(Generation, Vec<Option<usize>>)> {
+    GENERATIONS.iter()
+        .filter_map(|&generation| {
+            let in_row = |relation: Relation| {
+                relatives.iter().enumerate()
+                    .filter(move |(_, relative)| relative.relation == relation)
+                    .map(|(index, _)| Some(index))
+            };
+            let row: Vec<Option<usize>> = match generation {
+                Generation::Own => in_row(Relation::Sibling)
+                    .chain(std::iter::once(None))
+                    .chain(in_row(Relation::Spouse))
+                    .collect(),
+                _ => relatives.iter().enumerate()
+                    .filter(|(_, relative)| relative.relation.generation() == generation)
+                    .map(|(index, _)| Some(index))
+                    .collect(),
+            };
+            if row.is_empty() { None } else { Some((generation, row)) }
+        })
+        .collect()
+}
+
+/// Relatives that are characters, and so can be selected, as
+/// (row, column, relative index) in tree order.
+fn selectable(relatives: &[Relative], rows: &[(Generation, Vec<Option<usize>>)]) -> Vec<(usize, usize, usize)> {
+    rows.iter().enumerate()
+        .flat_map(|(row, (_, entries))| {
+            entries.iter().enumerate().filter_map(move |(column, entry)| Some((row, column, (*entry)?)))
+        })
+        .filter(|&(_, _, index)| relatives[index].character.is_some())
+        .collect()
+}
+
+/// First relative that can be selected.
+pub fn first_selectable(relatives: &[Relative]) -> Option<usize> {
+    selectable(relatives, &tree_rows(relatives)).first().map(|&(_, _, index)| index)
+}
+
+/// Moves the selection `delta` relatives along the tree, row by row.
+pub fn step(relatives: &[Relative], selected: Option<usize>, delta: isize) -> Option<usize> {
+    let order = selectable(relatives, &tree_rows(relatives));
+    if order.is_empty() {
+        return None;
+    }
+    let position = order.iter().position(|&(_, _, index)| Some(index) == selected).unwrap_or(0) as isize;
+    let position = (position + delta).clamp(0, order.len() as isize - 1) as usize;
+    Some(order[position].2)
+}
+
+/// Moves the selection to the closest relative in the nearest row above
+/// (negative `delta`) or below that has any.
+pub fn change_row(relatives: &[Relative], selected: Option<usize>, delta: isize) -> Option<usize> {
+    let order = selectable(relatives, &tree_rows(relatives));
+    let (row, column) = match order.iter().find(|&&(_, _, index)| Some(index) == selected) {
+        Some(&(row, column, _)) => (row as isize, column as isize),
+        None => return order.first().map(|&(_, _, index)| index),
+    };
+    let target_row = order.iter()
+        .map(|&(other_row, _, _)| other_row as isize)
+        .filter(|&other_row| if delta < 0 { other_row < row } else { other_row > row })
+        .min_by_key(|&other_row| (other_row - row).abs());
+    match target_row {
+        Some(target_row) => order.iter()
+            .filter(|&&(other_row, _, _)| other_row as isize == target_row)
+            .min_by_key(|&&(_, other_column, _)| (other_column as isize - column).abs())
+            .map(|&(_, _, index)| index),
+        None => selected,
+    }
+}
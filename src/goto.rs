@@ -0,0 +1,110 @@
+use crate::app::App;
+use crate::data::{Series, parse_release_date};
+use crate::i18n::Locale;
+
+/// Where a "go to" reference points.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GotoTarget {
+    Episode(usize, u32), // (series_index, episode_number)
+    Movie(u32),
+}
+
+/// Parses a "go to" reference:
+///
+/// - `42` is episode 42 of the current series (movie 42 in the Movies tab)
+/// - `dbz 150`, `gt 12`, `super 5` pick the series first
+/// - `#200` counts episodes across all series in airing order
+pub fn parse_goto(input: &str, app: &App) -> Result<GotoTarget, String> {
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+    match tokens.as_slice() {
+        [absolute] if absolute.starts_with('#') => {
+            let number = parse_number(&absolute[1..], app.locale)?;
+            absolute_episode(&app.guide, number, &app.region, app.locale)
+        }
+        [number] => {
+            let number = parse_number(number, app.locale)?;
+            if app.selected_tab == 1 {
+                if !app.movies.iter().any(|movie| movie.number == number) {
+                    return Err(app.locale.fill("No movie {}", &[&number]));
+                }
+                return Ok(GotoTarget::Movie(number));
+            }
+            episode_in_series(&app.guide, app.selected_series_tab, number, app.locale)
+        }
+        [series, number] => {
+            let series_index = find_series(&app.guide, series, app.locale)?;
+            episode_in_series(&app.guide, series_index, parse_number(number, app.locale)?, app.locale)
+        }
+        _ => Err(app.locale.fill("Cannot go to \"{}\"", &[&input.trim()])),
+    }
+}
+
+fn parse_number(s: &str, locale: Locale) -> Result<u32, String> {
+    s.parse().map_err(|_| locale.fill("Not a number: {}", &[&s]))
+}
+
+fn episode_in_series(guide: &[Series], series_index: usize, number: u32, locale: Locale) -> Result<GotoTarget, String> {
+    let series = guide.get(series_index).ok_or(locale.tr("No series selected"))?;
+    if series.episodes.iter().any(|ep| ep.episode_number == number) {
+        Ok(GotoTarget::Episode(series_index, number))
+    } else {
+        Err(locale.fill("{} has no episode {}", &[&series.series, &number]))
+    }
+}
+
+/// Short names a series can be referred to by, e.g. "dbz" and "z" for
+/// "Dragon Ball Z".
+pub fn series_aliases(name: &str) -> Vec<String> {
+    let lower = name.to_lowercase();
+    let mut aliases = vec![lower.clone(), lower.replace(' ', "")];
+    match lower.strip_prefix("dragon ball") {
+        Some("") => aliases.push("db".to_string()),
+        Some(rest) => {
+            let rest = rest.trim().replace(' ', "");
+            aliases.push(format!("db{}", rest));
+            if let Some(initial) = rest.chars().next() {
+                aliases.push(format!("db{}", initial));
+            }
+            aliases.push(rest);
+        }
+        None => {}
+    }
+    aliases
+}
+
+pub fn find_series(guide: &[Series], reference: &str, locale: Locale) -> Result<usize, String> {
+    let reference = reference.to_lowercase();
+    if let Some(index) = guide.iter().position(|series| series_aliases(&series.series).contains(&reference)) {
+        return Ok(index);
+    }
+    // Fall back to a unique prefix, e.g. "sup" for Super
+    let candidates: Vec<usize> = guide.iter().enumerate()
+        .filter(|(_, series)| series_aliases(&series.series).iter().any(|alias| alias.starts_with(&reference)))
+        .map(|(index, _)| index)
+        .collect();
+    match candidates.as_slice() {
+        [index] => Ok(*index),
+        [] => Err(locale.fill("Unknown series: {}", &[&reference])),
+        _ => Err(locale.fill("Ambiguous series: {}", &[&reference])),
+    }
+}
+
+/// Resolves the n-th episode of the franchise, counting the series in the
+/// order they first aired.
+fn absolute_episode(guide: &[Series], number: u32, region: &str, locale: Locale) -> Result<GotoTarget, String> {
+    let mut series_order: Vec<usize> = (0..guide.len()).collect();
+    series_order.sort_by_key(|&index| {
+        guide[index].episodes.iter().filter_map(|ep| parse_release_date(ep.release_date_in(region))).min()
+    });
+
+    let mut remaining = number as usize;
+    for series_index in series_order {
+        let mut numbers: Vec<u32> = guide[series_index].episodes.iter().map(|ep| ep.episode_number).collect();
+        numbers.sort_unstable();
+        if remaining >= 1 && remaining <= numbers.len() {
+            return Ok(GotoTarget::Episode(series_index, numbers[remaining - 1]));
+        }
+        remaining = remaining.saturating_sub(numbers.len());
+    }
+    Err(locale.fill("There is no episode #{}", &[&number]))
+}
@@ -1,183 +1,536 @@
-use crossterm::event::{KeyCode, KeyEvent};
-use crate::app::{App, AppMode, SearchResultType};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::app::{App, AppMode, Prompt, PromptKind, TAB_TITLES};
+use crate::user_data::MAX_NOTE_LENGTH;
+use crate::keymap::Action;
+use crate::palette::{Command, Palette};
+use crate::family::{change_row, relatives, step};
+use crate::timeline::lanes;
 
 pub fn handle_key_event(key: KeyEvent, app: &mut App) -> Result<bool, Box<dyn std::error::Error>> {
+    let keep_running = handle_key(key, app)?;
+    // Leaving the quiz for anything but the help screen ends the session
+    if app.quiz.is_some() && (!keep_running || !matches!(app.app_mode, AppMode::Quiz | AppMode::Help)) {
+        app.finish_quiz();
+    }
+    Ok(keep_running)
+}
+
+fn handle_key(key: KeyEvent, app: &mut App) -> Result<bool, Box<dyn std::error::Error>> {
+    if app.palette.is_some() {
+        return Ok(handle_palette_input(key, app));
+    }
+    if app.prompt.is_some() {
+        handle_prompt_input(key, app);
+        return Ok(true);
+    }
+    if app.on_this_day.is_some() {
+        handle_on_this_day_input(key, app);
+        return Ok(true);
+    }
+    if app.filter_panel.is_some() {
+        handle_filter_panel_input(key, app);
+        return Ok(true);
+    }
+
+    let action = app.keymap.action_for(key);
     match app.app_mode {
         AppMode::Help => {
-            if key.code == KeyCode::Esc || key.code == KeyCode::Char('H') || key.code == KeyCode::Char('h') {
-                app.app_mode = app.previous_mode.clone();
+            if matches!(action, Some(Action::Back) | Some(Action::Help)) && !app.go_back() {
+                app.app_mode = app.tab_mode();
             }
         }
         AppMode::Search => {
-            match key.code {
-                KeyCode::Esc => {
-                    // Exit search mode
-                    app.app_mode = match app.selected_tab {
-                        0 => AppMode::EpisodesSeries(app.selected_series_tab),
-                        1 => AppMode::MoviesList,
-                        2 => AppMode::Characters,
-                        _ => app.app_mode.clone(),
-                    };
-                    app.search_results.clear();
-                }
-                KeyCode::Char(c) => {
+            let is_text_input = matches!(key.code, KeyCode::Char(_))
+                && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+            if is_text_input {
+                if let KeyCode::Char(c) = key.code {
                     app.search_query.push(c.to_lowercase().next().unwrap_or(c));
                     app.perform_search();
                 }
-                KeyCode::Backspace => {
-                    app.search_query.pop();
-                    app.perform_search();
-                }
-                KeyCode::Enter => {
-                    if let Some(selected) = app.list_state.selected() {
-                        if let Some(result) = app.search_results.get(selected) {
-                            match result.result_type {
-                                SearchResultType::Episode(series_index, episode_index) => {
-                                    app.app_mode = AppMode::Details(series_index, episode_index);
-                                    app.selected_tab = 0;
-                                    app.selected_series_tab = series_index;
-                                }
-                                SearchResultType::Movie(movie_index) => {
-                                    app.app_mode = AppMode::MovieDetails(movie_index);
-                                    app.selected_tab = 1;
-                                }
-                            }
-                            app.search_results.clear();
-                        }
+                return Ok(true);
+            }
+            if key.code == KeyCode::Backspace {
+                app.search_query.pop();
+                app.perform_search();
+                return Ok(true);
+            }
+            match action {
+                Some(Action::Back) => {
+                    // Exit search mode
+                    if !app.go_back() {
+                        app.app_mode = app.tab_mode();
                     }
+                    app.search_results.clear();
                 }
-                KeyCode::Down => {
-                    if let Some(selected) = app.list_state.selected() {
-                        if selected < app.search_results.len() - 1 {
-                            app.list_state.select(Some(selected + 1));
-                        }
-                    } else {
-                        app.list_state.select(Some(0));
+                Some(Action::Select) => {
+                    let selected = app.list_state.selected()
+                        .and_then(|selected| app.search_results.get(selected))
+                        .map(|result| result.result_type.clone());
+                    if let Some(target) = selected {
+                        app.open_item(target);
+                        app.search_results.clear();
                     }
                 }
-                KeyCode::Up => {
-                    if let Some(selected) = app.list_state.selected() {
-                        if selected > 0 {
-                            app.list_state.select(Some(selected - 1));
-                        }
+                Some(
+                    action @ (Action::Up
+                    | Action::Down
+                    | Action::PageUp
+                    | Action::PageDown
+                    | Action::HalfPageUp
+                    | Action::HalfPageDown
+                    | Action::Top
+                    | Action::Bottom),
+                ) => move_in_list(action, 1, app),
+                _ => {}
+            }
+        }
+        AppMode::Quiz => {
+            // Unbound digits answer with that choice
+            if let (None, KeyCode::Char(c @ '1'..='9')) = (action, key.code) {
+                app.answer_quiz(c as usize - '1' as usize);
+                return Ok(true);
+            }
+            match (action, app.quiz.as_mut()) {
+                (Some(Action::Up), Some(quiz)) => quiz.move_selection(-1),
+                (Some(Action::Down), Some(quiz)) => quiz.move_selection(1),
+                (Some(Action::Select), Some(quiz)) => {
+                    if quiz.given.is_some() {
+                        app.next_quiz_question();
+                    } else {
+                        let selected = quiz.selected;
+                        app.answer_quiz(selected);
                     }
                 }
-                _ => {}
+                (Some(Action::Select), None) => app.start_quiz(),
+                (Some(action), _) => return Ok(perform_action(action, None, app)),
+                (None, _) => {}
             }
         }
         _ => {
-            match key.code {
-                KeyCode::Char(c) => {
-                    let lower_c = c.to_lowercase().next().unwrap_or(c);
-                    match lower_c {
-                        'q' => return Ok(false),
-                        'h' => {
-                            app.previous_mode = app.app_mode.clone();
-                            app.app_mode = AppMode::Help;
-                        }
-                        's' => {
-                            app.app_mode = AppMode::Search;
-                            app.search_query.clear();
-                        }
-                        'm' => {
-                            if app.selected_tab == 0 {
-                                app.toggle_episode_sort_method();
-                            } else if app.selected_tab == 1 {
-                                app.toggle_movie_sort_method();
-                            }
-                        }
-                        'o' => {
-                            if app.selected_tab == 0 {
-                                app.toggle_episode_sort_order();
-                            } else if app.selected_tab == 1 {
-                                app.toggle_movie_sort_order();
-                            }
-                        }
-                        _ => {}
-                    }
+            // Unbound digits build up a count prefix for the next action
+            if let (None, KeyCode::Char(c @ '0'..='9')) = (action, key.code) {
+                let digit = c as usize - '0' as usize;
+                if digit > 0 || app.pending_count.is_some() {
+                    app.pending_count = Some(app.pending_count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
                 }
-                KeyCode::Tab => {
-                    if !matches!(app.app_mode, AppMode::Details(_, _) | AppMode::MovieDetails(_)) {
-                        app.selected_tab = (app.selected_tab + 1) % 3;
-                        app.app_mode = match app.selected_tab {
-                            0 => AppMode::EpisodesSeries(app.selected_series_tab),
-                            1 => AppMode::MoviesList,
-                            2 => AppMode::Characters,
-                            _ => app.app_mode.clone(),
-                        };
-                        app.reset_list_state_for_tab();
-                    }
+                return Ok(true);
+            }
+            let count = app.pending_count.take();
+            if let Some(action) = action {
+                return Ok(perform_action(action, count, app));
+            }
+        }
+    }
+    Ok(true)
+}
+
+fn handle_palette_input(key: KeyEvent, app: &mut App) -> bool {
+    let palette = match app.palette.as_mut() {
+        Some(palette) => palette,
+        None => return true,
+    };
+    match key.code {
+        KeyCode::Esc => app.palette = None,
+        KeyCode::Enter => {
+            let command = palette.selected_command();
+            app.palette = None;
+            if let Some(command) = command {
+                return run_command(command, app);
+            }
+        }
+        KeyCode::Up => palette.move_selection(-1),
+        KeyCode::Down | KeyCode::Tab => palette.move_selection(1),
+        KeyCode::Backspace => {
+            palette.query.pop();
+            palette.refilter();
+        }
+        KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            palette.query.push(c);
+            palette.refilter();
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Runs a command picked from the palette. Returns `false` when the
+/// application should exit.
+pub fn run_command(command: Command, app: &mut App) -> bool {
+    match command {
+        Command::Action(action) => return perform_action(action, None, app),
+        Command::SwitchTab(tab) => {
+            if tab != app.selected_tab || app.app_mode != app.tab_mode() {
+                app.navigate(app.tab_mode_for(tab));
+                app.selected_tab = tab;
+                app.reset_list_state_for_tab();
+            }
+        }
+        Command::SortEpisodes(method) => app.set_episode_sort_method(method),
+        Command::SortMovies(method) => app.set_movie_sort_method(method),
+        Command::JumpToSeries(series_index) => app.open_series(series_index, None),
+        Command::OpenSaga(series_index, saga) => app.open_series(series_index, Some(saga)),
+        Command::SetTheme(theme_index) => app.set_theme(theme_index),
+        Command::ShowStats(page) => {
+            app.navigate(AppMode::Stats);
+            app.selected_tab = 4;
+            app.stats_page = page;
+            app.stats_scroll = 0;
+        }
+        Command::ShowOnThisDay => app.show_on_this_day(),
+        Command::Export => app.export(),
+    }
+    true
+}
+
+/// Up and Down pick an anniversary and Enter opens it; any other key closes
+/// the panel.
+fn handle_on_this_day_input(key: KeyEvent, app: &mut App) {
+    let panel = match app.on_this_day.as_mut() {
+        Some(panel) => panel,
+        None => return,
+    };
+    match app.keymap.action_for(key) {
+        Some(Action::Up) => panel.move_selection(-1),
+        Some(Action::Down) => panel.move_selection(1),
+        Some(Action::Select) => {
+            let target = panel.selected().map(|anniversary| anniversary.target.clone());
+            app.on_this_day = None;
+            if let Some(target) = target {
+                app.open_item(target);
+            }
+        }
+        _ => app.on_this_day = None,
+    }
+}
+
+/// Up and Down pick an option and Page Up/Down jump between kinds; Enter
+/// or Space picks or drops it and Backspace drops them all. Any other key
+/// closes the panel.
+fn handle_filter_panel_input(key: KeyEvent, app: &mut App) {
+    let panel = match app.filter_panel.as_mut() {
+        Some(panel) => panel,
+        None => return,
+    };
+    if key.code == KeyCode::Char(' ') {
+        app.toggle_filter_option();
+        return;
+    }
+    if key.code == KeyCode::Backspace {
+        app.clear_filter();
+        return;
+    }
+    match app.keymap.action_for(key) {
+        Some(Action::Up) => panel.move_selection(-1),
+        Some(Action::Down) => panel.move_selection(1),
+        Some(Action::PageUp) => panel.jump_kind(false),
+        Some(Action::PageDown) => panel.jump_kind(true),
+        Some(Action::Select) => app.toggle_filter_option(),
+        _ => app.filter_panel = None,
+    }
+}
+
+fn handle_prompt_input(key: KeyEvent, app: &mut App) {
+    let prompt = match app.prompt.as_mut() {
+        Some(prompt) => prompt,
+        None => return,
+    };
+    match key.code {
+        KeyCode::Esc => app.prompt = None,
+        KeyCode::Enter => {
+            let prompt = match app.prompt.take() {
+                Some(prompt) => prompt,
+                None => return,
+            };
+            match prompt.kind {
+                PromptKind::Find => {
+                    app.find_query = prompt.input;
+                    app.find_in_list(true);
                 }
-                KeyCode::Left | KeyCode::Right => {
-                    if !matches!(app.app_mode, AppMode::Details(_, _) | AppMode::MovieDetails(_)) {
-                        if app.selected_tab == 0 {
-                            let num_series = app.guide.len();
-                            if key.code == KeyCode::Left {
-                                app.selected_series_tab = (app.selected_series_tab + num_series - 1) % num_series;
-                            } else {
-                                app.selected_series_tab = (app.selected_series_tab + 1) % num_series;
-                            }
-                            app.app_mode = AppMode::EpisodesSeries(app.selected_series_tab);
-                            app.reset_list_state_for_tab();
-                        }
-                    }
+                PromptKind::GoTo => app.go_to(&prompt.input),
+                PromptKind::Note(key) => app.set_note(key, &prompt.input),
+                PromptKind::Rating(key) => app.set_rating(key, &prompt.input),
+                PromptKind::RandomFilter => app.set_random_filter(&prompt.input),
+            }
+        }
+        KeyCode::Backspace => {
+            prompt.input.pop();
+        }
+        KeyCode::Char(_) if matches!(prompt.kind, PromptKind::Note(_))
+            && prompt.input.chars().count() >= MAX_NOTE_LENGTH => {}
+        KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+            prompt.input.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// Runs `action` against the browsing views, repeating or targeting it
+/// according to a vim-style `count` prefix. Returns `false` when the
+/// application should exit.
+pub fn perform_action(action: Action, count: Option<usize>, app: &mut App) -> bool {
+    let in_details = matches!(
+        app.app_mode,
+        AppMode::Details(_, _)
+            | AppMode::MovieDetails(_)
+            | AppMode::SagaDetails(_, _)
+            | AppMode::CharacterDetails(_)
+            | AppMode::FamilyTree(_)
+            | AppMode::CharacterComparison(_, _)
+    );
+    let repeat = count.unwrap_or(1);
+    if app.app_mode == AppMode::Timeline && move_in_timeline(action, repeat, app) {
+        return true;
+    }
+    if let AppMode::FamilyTree(character_index) = app.app_mode {
+        if move_in_family_tree(action, character_index, app) {
+            return true;
+        }
+    }
+    match action {
+        Action::Quit => return false,
+        Action::Help => app.navigate(AppMode::Help),
+        Action::CommandPalette => app.palette = Some(Palette::new(app)),
+        Action::Search => {
+            app.navigate(AppMode::Search);
+            app.search_query.clear();
+            app.search_results.clear();
+        }
+        Action::Forward => {
+            app.go_forward();
+        }
+        Action::ToggleWatched => app.toggle_watched(),
+        Action::ToggleFavorite => app.toggle_favorite(),
+        Action::EditNote => {
+            if let Some((key, _)) = app.selected_item() {
+                let input = app.user_data.note(&key).unwrap_or_default().to_string();
+                app.prompt = Some(Prompt { kind: PromptKind::Note(key), input });
+            }
+        }
+        Action::Rate => {
+            if let Some((key, _)) = app.selected_episode_or_movie() {
+                let input = app.user_data.rating(&key).map(|rating| rating.to_string()).unwrap_or_default();
+                app.prompt = Some(Prompt { kind: PromptKind::Rating(key), input });
+            }
+        }
+        Action::Random => app.pick_random(),
+        Action::Quiz => app.start_quiz(),
+        Action::FamilyTree => match app.app_mode {
+            AppMode::Characters => {
+                if let Some(character_index) = app.selected_character() {
+                    app.open_family_tree(character_index);
+                }
+            }
+            AppMode::CharacterDetails(character_index) => app.open_family_tree(character_index),
+            _ => {}
+        },
+        Action::Compare => match app.app_mode {
+            AppMode::Characters => {
+                if let Some(character_index) = app.selected_character() {
+                    app.compare_character(character_index);
+                }
+            }
+            AppMode::CharacterDetails(character_index) => app.compare_character(character_index),
+            _ => {}
+        },
+        Action::Filter => app.open_filter_panel(),
+        Action::RandomFilter => {
+            let input = app.random_filter.describe(&app.guide);
+            app.prompt = Some(Prompt { kind: PromptKind::RandomFilter, input });
+        }
+        Action::CycleTheme => app.cycle_theme(),
+        Action::SortMethod => {
+            if app.selected_tab == 0 {
+                app.toggle_episode_sort_method();
+            } else if app.selected_tab == 1 {
+                app.toggle_movie_sort_method();
+            }
+        }
+        Action::SortOrder => {
+            if app.selected_tab == 0 {
+                app.toggle_episode_sort_order();
+            } else if app.selected_tab == 1 {
+                app.toggle_movie_sort_order();
+            }
+        }
+        Action::NextTab => {
+            if !in_details {
+                let next_tab = (app.selected_tab + 1) % TAB_TITLES.len();
+                app.navigate(app.tab_mode_for(next_tab));
+                app.selected_tab = next_tab;
+                app.reset_list_state_for_tab();
+            }
+        }
+        Action::PrevSeries | Action::NextSeries if app.app_mode == AppMode::Stats => {
+            app.step_stats_page(if action == Action::PrevSeries { -1 } else { 1 });
+        }
+        Action::Up | Action::Down if app.app_mode == AppMode::Stats => {
+            app.stats_scroll = if action == Action::Up {
+                app.stats_scroll.saturating_sub(repeat as u16)
+            } else {
+                app.stats_scroll.saturating_add(repeat as u16)
+            };
+        }
+        Action::PrevSeries | Action::NextSeries => {
+            if !in_details && app.selected_tab == 0 {
+                let num_series = app.guide.len();
+                if action == Action::PrevSeries {
+                    app.selected_series_tab = (app.selected_series_tab + num_series - 1) % num_series;
+                } else {
+                    app.selected_series_tab = (app.selected_series_tab + 1) % num_series;
                 }
-                KeyCode::Down => {
-                    if let Some(selected) = app.list_state.selected() {
-                        let count = match app.selected_tab {
-                            0 => app.guide[app.selected_series_tab].episodes.len(),
-                            1 => app.movies.len(),
-                            _ => 0,
-                        };
-                        if selected < count - 1 {
-                            app.list_state.select(Some(selected + 1));
-                        }
+                app.app_mode = AppMode::EpisodesSeries(app.selected_series_tab);
+                app.reset_list_state_for_tab();
+            }
+        }
+        Action::Up
+        | Action::Down
+        | Action::PageUp
+        | Action::PageDown
+        | Action::HalfPageUp
+        | Action::HalfPageDown => move_in_list(action, repeat, app),
+        Action::Top | Action::Bottom => {
+            if in_details {
+                return true;
+            }
+            match count {
+                Some(number) => app.select_number(number),
+                None => move_in_list(action, 1, app),
+            }
+        }
+        Action::FindInList => {
+            if !in_details {
+                app.prompt = Some(Prompt { kind: PromptKind::Find, input: String::new() });
+            }
+        }
+        Action::GoTo => {
+            if !in_details {
+                app.prompt = Some(Prompt { kind: PromptKind::GoTo, input: String::new() });
+            }
+        }
+        // Only meaningful in the Timeline tab, handled above
+        Action::Zoom => {}
+        Action::FindNext | Action::FindPrev => {
+            if !in_details {
+                app.find_in_list(action == Action::FindNext);
+            }
+        }
+        Action::Back => {
+            if app.go_back() {
+                return true;
+            }
+            match app.app_mode {
+                AppMode::Details(_, _) | AppMode::SagaDetails(_, _) => {
+                    app.app_mode = AppMode::EpisodesSeries(app.selected_series_tab);
+                }
+                AppMode::MovieDetails(_) => {
+                    app.app_mode = AppMode::MoviesList;
+                }
+                AppMode::CharacterDetails(_) => {
+                    app.app_mode = AppMode::Characters;
+                }
+                AppMode::FamilyTree(character_index) => {
+                    app.app_mode = AppMode::CharacterDetails(character_index);
+                }
+                AppMode::CharacterComparison(_, _) => {
+                    app.app_mode = AppMode::Characters;
+                }
+                _ => {}
+            }
+        }
+        Action::Select => {
+            match app.app_mode {
+                AppMode::EpisodesSeries(series_index) => {
+                    if let Some(episode_index) = app.selected_episode(series_index) {
+                        app.navigate(AppMode::Details(series_index, episode_index));
                     }
                 }
-                KeyCode::Up => {
-                    if let Some(selected) = app.list_state.selected() {
-                        if selected > 0 {
-                            app.list_state.select(Some(selected - 1));
-                        }
+                AppMode::MoviesList => {
+                    if let Some(movie_index) = app.selected_movie() {
+                        app.navigate(AppMode::MovieDetails(movie_index));
                     }
                 }
-                KeyCode::Esc => {
-                    match app.app_mode {
-                        AppMode::Details(_, _) => {
-                            app.app_mode = AppMode::EpisodesSeries(app.selected_series_tab);
-                        }
-                        AppMode::MovieDetails(_) => {
-                            app.app_mode = AppMode::MoviesList;
-                        }
-                        AppMode::Search => {
-                            app.app_mode = match app.selected_tab {
-                                0 => AppMode::EpisodesSeries(app.selected_series_tab),
-                                1 => AppMode::MoviesList,
-                                2 => AppMode::Characters,
-                                _ => app.app_mode.clone(),
-                            };
-                        }
-                        _ => {}
+                AppMode::Characters => {
+                    if let Some(character_index) = app.selected_character() {
+                        app.navigate(AppMode::CharacterDetails(character_index));
                     }
                 }
-                KeyCode::Enter => {
-                    match app.app_mode {
-                        AppMode::EpisodesSeries(series_index) => {
-                            if let Some(episode_index) = app.list_state.selected() {
-                                app.app_mode = AppMode::Details(series_index, episode_index);
-                            }
-                        }
-                        AppMode::MoviesList => {
-                            if let Some(movie_index) = app.list_state.selected() {
-                                app.app_mode = AppMode::MovieDetails(movie_index);
-                            }
-                        }
-                        _ => {}
+                AppMode::Favorites => {
+                    let favorite = app.list_state.selected()
+                        .and_then(|selected| app.favorites().into_iter().nth(selected));
+                    if let Some(favorite) = favorite {
+                        app.open_item(favorite.target);
                     }
                 }
                 _ => {}
             }
         }
     }
-    Ok(true)
-}
\ No newline at end of file
+    true
+}
+
+/// Moves the selection of the current list for a movement action, `repeat`
+/// times. Pages are sized to the rows visible in the last drawn list.
+fn move_in_list(action: Action, repeat: usize, app: &mut App) {
+    let page = app.list_height.max(1);
+    let rows = match action {
+        Action::Up | Action::Down => 1,
+        Action::PageUp | Action::PageDown => page,
+        Action::HalfPageUp | Action::HalfPageDown => (page / 2).max(1),
+        Action::Top => return app.select_first(),
+        Action::Bottom => return app.select_last(),
+        _ => return,
+    };
+    let delta = (rows * repeat) as isize;
+    match action {
+        Action::Up | Action::PageUp | Action::HalfPageUp => app.move_selection(-delta),
+        _ => app.move_selection(delta),
+    }
+}
+
+/// Handles the actions that move around the Timeline tab: Left/Right step
+/// through the releases of a lane, Up/Down switch lanes. Returns `false` for
+/// other actions.
+fn move_in_timeline(action: Action, repeat: usize, app: &mut App) -> bool {
+    let lanes = lanes(app);
+    let timeline = &mut app.timeline;
+    match action {
+        Action::PrevSeries => timeline.step(&lanes, -(repeat as isize)),
+        Action::NextSeries => timeline.step(&lanes, repeat as isize),
+        Action::Up => timeline.change_lane(&lanes, -(repeat as isize)),
+        Action::Down => timeline.change_lane(&lanes, repeat as isize),
+        Action::Top => timeline.select_first(),
+        Action::Bottom => timeline.select_last(&lanes),
+        Action::PageUp | Action::PageDown => timeline.jump(&lanes, action == Action::PageDown),
+        Action::Zoom => timeline.zoom = timeline.zoom.toggle(),
+        Action::Select => {
+            if let Some(item) = timeline.selected(&lanes) {
+                app.open_item(item.target.clone());
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Handles the actions that move around a family tree: Left/Right step
+/// through the relatives, Up/Down change generation and Select opens the
+/// family tree of the selected relative. Returns `false` for any other
+/// action.
+fn move_in_family_tree(action: Action, character_index: usize, app: &mut App) -> bool {
+    let relatives = relatives(&app.characters, character_index);
+    let selected = app.list_state.selected();
+    match action {
+        Action::PrevSeries => app.list_state.select(step(&relatives, selected, -1)),
+        Action::NextSeries => app.list_state.select(step(&relatives, selected, 1)),
+        Action::Up => app.list_state.select(change_row(&relatives, selected, -1)),
+        Action::Down => app.list_state.select(change_row(&relatives, selected, 1)),
+        Action::Select => {
+            let relative = selected.and_then(|index| relatives.get(index)).and_then(|relative| relative.character);
+            if let Some(relative) = relative {
+                app.open_family_tree(relative);
+            }
+        }
+        _ => return false,
+    }
+    true
+}
@@ -0,0 +1,611 @@
+use std::env;
+use std::fmt::Display;
+
+/// Language of the interface text. The episodes, movies and characters
+/// keep the language of their data files.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// Reads locale codes such as "es", "es_ES.UTF-8" or "en-US".
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['_', '-', '.', '@']).next().unwrap_or("").to_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::English),
+            "es" => Some(Locale::Spanish),
+            _ => None,
+        }
+    }
+
+    /// The locale set in the config, or else the one of the environment
+    /// (`LC_ALL`, `LC_MESSAGES`, then `LANG`). Locales without a catalog
+    /// fall back to English.
+    pub fn detect(configured: Option<&str>) -> Locale {
+        let code = match configured {
+            Some(code) => Some(code.to_string()),
+            None => ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+                .filter_map(|var| env::var(var).ok())
+                .find(|value| !value.is_empty()),
+        };
+        code.and_then(|code| Locale::from_code(&code)).unwrap_or_default()
+    }
+
+    /// `text` in this locale. Texts are looked up by their English wording,
+    /// which is also shown when the catalog lacks a translation.
+    pub fn tr(self, text: &'static str) -> &'static str {
+        let translated = match self {
+            Locale::English => None,
+            Locale::Spanish => spanish(text),
+        };
+        translated.unwrap_or(text)
+    }
+
+    /// Like `tr` for a text with `{}` placeholders, filled with `args` in
+    /// order.
+    pub fn fill(self, template: &'static str, args: &[&dyn Display]) -> String {
+        let mut text = String::new();
+        let mut args = args.iter();
+        for (index, part) in self.tr(template).split("{}").enumerate() {
+            if index > 0 {
+                if let Some(arg) = args.next() {
+                    text.push_str(&arg.to_string());
+                }
+            }
+            text.push_str(part);
+        }
+        text
+    }
+}
+
+fn spanish(text: &str) -> Option<&'static str> {
+    let translated = match text {
+        // Tabs and pages
+        "Main Tabs" => "Pestañas principales",
+        "Series Tabs" => "Series",
+        "Stats Pages" => "Páginas de estadísticas",
+        "Episodes" => "Episodios",
+        "Movies" => "Películas",
+        "Characters" => "Personajes",
+        "Favorites" => "Favoritos",
+        "Stats" => "Estadísticas",
+        "Timeline" => "Cronología",
+        "Episodes per year" => "Episodios por año",
+        "Sagas" => "Sagas",
+        "Watch progress" => "Progreso",
+
+        // Details
+        " Episode Details: {} " => " Detalles del episodio: {} ",
+        " Movie Details: {} " => " Detalles de la película: {} ",
+        " Character Details: {} " => " Detalles del personaje: {} ",
+        " Saga: {} " => " Saga: {} ",
+        " Family Tree: {} " => " Árbol familiar: {} ",
+        "Episode Number: " => "Número de episodio: ",
+        "Release Date: " => "Fecha de estreno: ",
+        "Other Releases: " => "Otros estrenos: ",
+        "{} ({}, {} dub)" => "{} ({}, doblaje de {})",
+        "Japan" => "Japón",
+        "Duration: " => "Duración: ",
+        "Saga: " => "Saga: ",
+        "Description: " => "Descripción: ",
+        "English Title: " => "Título en inglés: ",
+        "Japanese Title: " => "Título japonés: ",
+        "Also Known As: " => "También conocido como: ",
+        "Related Movies:" => "Películas relacionadas:",
+        " (non-canon)" => " (no canónica)",
+        "canon" => "canónica",
+        "non-canon" => "no canónica",
+        ", around episode {}" => ", en torno al episodio {}",
+        ", around episodes {}-{}" => ", en torno a los episodios {}-{}",
+        ", no fixed placement" => ", sin lugar fijo",
+        "Series: " => "Serie: ",
+        "Episodes: " => "Episodios: ",
+        "{}-{} ({} episodes)" => "{}-{} ({} episodios)",
+        "Aired: " => "Emisión: ",
+        "Runtime: " => "Duración: ",
+        "Watched: " => "Vistos: ",
+        "Number: " => "Número: ",
+        "Director: " => "Director: ",
+        "Genres: " => "Géneros: ",
+        "Takes Place: " => "Transcurre: ",
+        "Trivia: " => "Curiosidades: ",
+        "Plot Keywords: " => "Palabras clave: ",
+        "Aliases: " => "Alias: ",
+        "Race: " => "Raza: ",
+        "Occupation: " => "Ocupación: ",
+        "Family: " => "Familia: ",
+        "Powers: " => "Poderes: ",
+        "Key Events: " => "Momentos clave: ",
+        "Favorite: " => "Favorito: ",
+        "yes" => "sí",
+        "Your Rating: " => "Tu puntuación: ",
+        "Your Note: " => "Tu nota: ",
+
+        // Lists
+        "Episodes " => "Episodios ",
+        "Movies " => "Películas ",
+        " {} of {}: {}" => " {} de {}: {}",
+        "Characters ({} of {}: {})" => "Personajes ({} de {}: {})",
+        "  [comparing]" => "  [comparando]",
+        "Favorites (press {} on an episode, movie or character to add one)" => "Favoritos (pulsa {} en un episodio, película o personaje para añadirlo)",
+        "Search" => "Buscar",
+        "Results" => "Resultados",
+        "Episode" => "Episodio",
+        "Movie" => "Película",
+        "Character" => "Personaje",
+        "Ep#" => "Ep#",
+        "Num" => "Núm",
+        "Title" => "Título",
+        "Date" => "Fecha",
+        "Rating" => "Puntuación",
+
+        // Characters
+        "Race" => "Raza",
+        "Series" => "Series",
+        "Occupation" => "Ocupación",
+        "Powers" => "Poderes",
+        "Key Events" => "Momentos clave",
+        " {} vs {}: {} shared powers " => " {} contra {}: {} poderes en común ",
+        "Grandparents" => "Abuelos",
+        "Parents" => "Padres",
+        "Children" => "Hijos",
+        "Grandchildren" => "Nietos",
+        "Other" => "Otros",
+        "No known family" => "Sin familia conocida",
+        "Genres" => "Géneros",
+        "Directors" => "Directores",
+        "Plot Keywords" => "Palabras clave",
+
+        // Popups
+        "Command Palette" => "Paleta de comandos",
+        "{} open  any other key closes" => "{} abrir  cualquier otra tecla cierra",
+        "Any key closes" => "Cualquier tecla cierra",
+        "On This Day: {}" => "Un día como hoy: {}",
+        "Today" => "Hoy",
+        "1 year ago" => "hace 1 año",
+        "{} years ago" => "hace {} años",
+        "Show Movies With" => "Mostrar películas con",
+        "{} show  {} close" => "{} mostrar  {} cerrar",
+        "Filter {}: {} of {}" => "Filtrar {}: {} de {}",
+        "{} pick  Backspace clear  {} close" => "{} elegir  Retroceso limpiar  {} cerrar",
+        "any other key" => "cualquier otra tecla",
+
+        // Command palette
+        "Switch to {} tab" => "Ir a la pestaña {}",
+        "Jump to series: {}" => "Ir a la serie: {}",
+        "Sort episodes by {}" => "Ordenar episodios por {}",
+        "Sort movies by {}" => "Ordenar películas por {}",
+        "episode number" => "número de episodio",
+        "number" => "número",
+        "title" => "título",
+        "release date" => "fecha de estreno",
+        "rating" => "puntuación",
+        "Open saga: {}" => "Abrir saga: {}",
+        "Use theme: {}" => "Usar tema: {}",
+        "Show stats: {}" => "Ver estadísticas: {}",
+        "Show on this day" => "Ver un día como hoy",
+        "Export episodes, movies and favorites" => "Exportar episodios, películas y favoritos",
+
+        // Status bar
+        "Go to (42, dbz 150, #300): " => "Ir a (42, dbz 150, #300): ",
+        "Note: " => "Nota: ",
+        "Rating (1-10, empty to clear): " => "Puntuación (1-10, vacío para borrar): ",
+        "Random from (dbz saga:frieza 1989-1995 max:30m unwatched canon): " => "Al azar entre (dbz saga:frieza 1989-1995 max:30m unwatched canon): ",
+        "EPISODES" => "EPISODIOS",
+        "EPISODE" => "EPISODIO",
+        "MOVIES" => "PELÍCULAS",
+        "MOVIE" => "PELÍCULA",
+        "SEARCH" => "BÚSQUEDA",
+        "SAGA" => "SAGA",
+        "CHARACTERS" => "PERSONAJES",
+        "CHARACTER" => "PERSONAJE",
+        "FAMILY" => "FAMILIA",
+        "COMPARE" => "COMPARAR",
+        "FAVORITES" => "FAVORITOS",
+        "STATS" => "ESTADÍSTICAS",
+        "TIMELINE" => "CRONOLOGÍA",
+        "QUIZ" => "TRIVIA",
+        "HELP" => "AYUDA",
+        "episode {}/{}" => "episodio {}/{}",
+        "movie {}/{}" => "película {}/{}",
+        "result {}/{}" => "resultado {}/{}",
+        "character {}/{}" => "personaje {}/{}",
+        "favorite {}/{}" => "favorito {}/{}",
+        "page {}/{}" => "página {}/{}",
+        "score {}/{}" => "aciertos {}/{}",
+        "sort: {}" => "orden: {}",
+        "watched: {}%" => "visto: {}%",
+
+        // Status messages
+        "Marked \"{}\" as watched" => "\"{}\" marcado como visto",
+        "Marked \"{}\" as not watched" => "\"{}\" marcado como no visto",
+        "Could not save watched state: {}" => "No se pudo guardar lo visto: {}",
+        "Added \"{}\" to favorites" => "\"{}\" añadido a favoritos",
+        "Removed \"{}\" from favorites" => "\"{}\" quitado de favoritos",
+        "Could not save favorites: {}" => "No se pudieron guardar los favoritos: {}",
+        "Saved note" => "Nota guardada",
+        "Removed note" => "Nota borrada",
+        "Could not save note: {}" => "No se pudo guardar la nota: {}",
+        "Ratings go from 1 to {}" => "Las puntuaciones van de 1 a {}",
+        "Rated {}/{}" => "Puntuado con {}/{}",
+        "Removed rating" => "Puntuación borrada",
+        "Could not save rating: {}" => "No se pudo guardar la puntuación: {}",
+        "Nothing matches the random filters" => "Nada cumple los filtros aleatorios",
+        "Picked 1 of {}" => "Elegido 1 de {}",
+        "Filters narrow the Characters and Movies lists" => "Los filtros acotan las listas de personajes y de películas",
+        "Comparing {}: pick another character" => "Comparando a {}: elige otro personaje",
+        "Comparison cancelled" => "Comparación cancelada",
+        "Not enough data to make up a quiz" => "No hay datos suficientes para una trivia",
+        "Right!" => "¡Correcto!",
+        "Wrong!" => "¡Incorrecto!",
+        "New best quiz score: {} of {} right" => "Nueva mejor puntuación: {} de {} aciertos",
+        "Quiz over: {} of {} right" => "Fin de la trivia: {} de {} aciertos",
+        "Could not save quiz score: {}" => "No se pudo guardar la puntuación de la trivia: {}",
+        "Cleared the movie filter" => "Filtro de películas quitado",
+        "No anniversaries today or in the coming days" => "No hay aniversarios hoy ni en los próximos días",
+        "Exported to {}" => "Exportado a {}",
+        "Export failed: {}" => "Error al exportar: {}",
+
+        // Go to and random filter errors
+        "Not a number: {}" => "No es un número: {}",
+        "No movie {}" => "No hay película {}",
+        "No series selected" => "No hay ninguna serie seleccionada",
+        "{} has no episode {}" => "{} no tiene episodio {}",
+        "Cannot go to \"{}\"" => "No se puede ir a \"{}\"",
+        "Unknown series: {}" => "Serie desconocida: {}",
+        "Ambiguous series: {}" => "Serie ambigua: {}",
+        "There is no episode #{}" => "No existe el episodio #{}",
+        "saga: needs part of a saga name" => "saga: necesita parte del nombre de una saga",
+        "Not a duration: {}" => "No es una duración: {}",
+        "Not a year: {}" => "No es un año: {}",
+        "Not a year range: {}" => "No es un rango de años: {}",
+        "Years go the wrong way: {}" => "Los años están al revés: {}",
+        "Pick either episodes or movies" => "Elige episodios o películas",
+        "Movies have no series or saga to filter by" => "Las películas no tienen serie ni saga por la que filtrar",
+
+        // Command line
+        "Not a number of days: {}" => "No es un número de días: {}",
+        "Days go up to {}" => "Los días llegan hasta {}",
+        "On this day, {}:" => "Un día como hoy, {}:",
+        "Nothing was released on this day." => "Nada se estrenó en este día.",
+        "Coming up in the next {} days:" => "En los próximos {} días:",
+        "No anniversaries." => "No hay aniversarios.",
+        "{}, {}, directed by {}" => "{}, {}, dirigida por {}",
+        "Invalid random_filter: {}" => "random_filter no válido: {}",
+
+        // Key hints
+        "tab" => "pestaña",
+        "series" => "serie",
+        "move" => "mover",
+        "open" => "abrir",
+        "back" => "atrás",
+        "forward" => "adelante",
+        "top" => "inicio",
+        "bottom" => "final",
+        "scroll" => "desplazar",
+        "page" => "página",
+        "go to" => "ir a",
+        "zoom" => "zoom",
+        "find" => "encontrar",
+        "next match" => "siguiente",
+        "quit" => "salir",
+        "help" => "ayuda",
+        "search" => "buscar",
+        "commands" => "comandos",
+        "watched" => "visto",
+        "star" => "favorito",
+        "note" => "nota",
+        "rate" => "puntuar",
+        "re-roll" => "otra vez",
+        "filters" => "filtros",
+        "quiz" => "trivia",
+        "family" => "familia",
+        "compare" => "comparar",
+        "filter" => "filtrar",
+        "theme" => "tema",
+        "sort" => "ordenar",
+        "order" => "orden",
+        "release" => "estreno",
+        "lane" => "carril",
+        "their tree" => "su árbol",
+        "relative" => "pariente",
+        "generation" => "generación",
+        "similar" => "similares",
+        "answer" => "responder",
+        "choice" => "opción",
+        "stop" => "parar",
+
+        // Help
+        "Help" => "Ayuda",
+        "Navigation" => "Navegación",
+        "Find in list" => "Buscar en la lista",
+        "Actions" => "Acciones",
+        "Sorting" => "Orden",
+        "Switch between main tabs" => "Cambiar entre las pestañas principales",
+        "Previous series (in Episodes tab)" => "Serie anterior (en la pestaña Episodios)",
+        "Next series (in Episodes tab)" => "Serie siguiente (en la pestaña Episodios)",
+        "Move up in lists" => "Subir en las listas",
+        "Move down in lists" => "Bajar en las listas",
+        "View details of selected item" => "Ver los detalles del elemento seleccionado",
+        "Go back to the previous view" => "Volver a la vista anterior",
+        "Go forward again after going back" => "Avanzar de nuevo tras volver atrás",
+        "Jump to first item (or to item N with a count)" => "Ir al primer elemento (o al elemento N con un número)",
+        "Jump to last item (or to item N with a count)" => "Ir al último elemento (o al elemento N con un número)",
+        "Scroll up half a page" => "Subir media página",
+        "Scroll down half a page" => "Bajar media página",
+        "Scroll up a page" => "Subir una página",
+        "Scroll down a page" => "Bajar una página",
+        "Go to an episode by number (42, dbz 150, #300)" => "Ir a un episodio por número (42, dbz 150, #300)",
+        "Zoom the timeline between years and months" => "Alternar la cronología entre años y meses",
+        "Find a title in the current list" => "Buscar un título en la lista actual",
+        "Jump to next match" => "Ir a la siguiente coincidencia",
+        "Jump to previous match" => "Ir a la coincidencia anterior",
+        "Quit the application" => "Salir de la aplicación",
+        "Toggle this help screen" => "Mostrar u ocultar esta ayuda",
+        "Enter search mode" => "Entrar en el modo de búsqueda",
+        "Open the command palette" => "Abrir la paleta de comandos",
+        "Mark selected episode or movie as (not) watched" => "Marcar el episodio o la película seleccionados como (no) vistos",
+        "Add selected item to (or remove it from) favorites" => "Añadir el elemento seleccionado a favoritos (o quitarlo)",
+        "Write a note about selected item" => "Escribir una nota sobre el elemento seleccionado",
+        "Rate selected episode or movie from 1 to 10" => "Puntuar el episodio o la película seleccionados del 1 al 10",
+        "Open a random episode or movie (again to re-roll)" => "Abrir un episodio o una película al azar (otra vez para repetir)",
+        "Choose what random picks can land on" => "Elegir entre qué se escoge al azar",
+        "Start a trivia quiz" => "Empezar una trivia",
+        "Show the family tree of selected character" => "Mostrar el árbol familiar del personaje seleccionado",
+        "Compare selected character with another one" => "Comparar el personaje seleccionado con otro",
+        "Filter the characters or movies list" => "Filtrar la lista de personajes o de películas",
+        "Switch to the next color theme" => "Cambiar al siguiente tema de colores",
+        "Change sort method" => "Cambiar el criterio de orden",
+        "Toggle sort order" => "Invertir el orden",
+
+        // Quiz
+        "Quiz" => "Trivia",
+        "Quiz: {} of {} right" => "Trivia: {} de {} aciertos",
+        "Press {} for the next question" => "Pulsa {} para la siguiente pregunta",
+        "Press {} to start a new quiz" => "Pulsa {} para empezar una nueva trivia",
+        "High Scores" => "Mejores puntuaciones",
+        "Which saga is {} episode {}, \"{}\", part of?" => "¿De qué saga forma parte {}, episodio {}, \"{}\"?",
+        "In what year was \"{}\" released?" => "¿En qué año se estrenó \"{}\"?",
+        "Who directed \"{}\"?" => "¿Quién dirigió \"{}\"?",
+        "Which movie is this about? \"{}\"" => "¿De qué película se habla? \"{}\"",
+        "Which character is also known as {}?" => "¿Qué personaje también es conocido como {}?",
+        "What race is {}?" => "¿De qué raza es {}?",
+
+        // Stats
+        "No release dates to chart." => "No hay fechas de estreno que mostrar.",
+        "Runtime per saga" => "Duración por saga",
+        "Longest" => "Más largas",
+        "Shortest" => "Más cortas",
+        "Longest and shortest sagas" => "Sagas más largas y más cortas",
+        "{} eps, {}" => "{} eps, {}",
+        "Movies per director" => "Películas por director",
+        "Movies per genre" => "Películas por género",
+        "Characters per race" => "Personajes por raza",
+        "Watched over time" => "Vistos a lo largo del tiempo",
+        "Nothing watched yet. Press {} on an episode or movie to mark it." => "Aún no has visto nada. Pulsa {} en un episodio o una película para marcarlo.",
+        "Watched per series" => "Vistos por serie",
+
+        // Timeline
+        "Timeline [{}]" => "Cronología [{}]",
+        "years" => "años",
+        "months" => "meses",
+        "Movie {}: {}" => "Película {}: {}",
+        "Selected" => "Seleccionado",
+        " (original release)" => " (estreno original)",
+        _ => return None,
+    };
+    Some(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_code_reads_the_language() {
+        assert_eq!(Locale::from_code("es"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("es_ES.UTF-8"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("ES-mx"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("en_US.UTF-8"), Some(Locale::English));
+        assert_eq!(Locale::from_code("C"), Some(Locale::English));
+        assert_eq!(Locale::from_code("C.UTF-8"), Some(Locale::English));
+        assert_eq!(Locale::from_code("POSIX"), Some(Locale::English));
+        assert_eq!(Locale::from_code("fr_FR.UTF-8"), None);
+        assert_eq!(Locale::from_code(""), None);
+    }
+
+    // The environment is shared by every test thread, so all the cases that
+    // change it live in this one test.
+    #[test]
+    fn detect_prefers_the_config_then_the_environment() {
+        let vars = ["LC_ALL", "LC_MESSAGES", "LANG"];
+        let saved: Vec<_> = vars.iter().map(env::var_os).collect();
+        for var in vars {
+            env::remove_var(var);
+        }
+
+        assert_eq!(Locale::detect(None), Locale::English);
+        env::set_var("LANG", "es_ES.UTF-8");
+        assert_eq!(Locale::detect(None), Locale::Spanish);
+        assert_eq!(Locale::detect(Some("en")), Locale::English);
+        env::set_var("LC_ALL", "C");
+        assert_eq!(Locale::detect(None), Locale::English);
+        env::set_var("LC_ALL", "");
+        assert_eq!(Locale::detect(None), Locale::Spanish);
+        env::set_var("LANG", "fr_FR.UTF-8");
+        assert_eq!(Locale::detect(None), Locale::English);
+        assert_eq!(Locale::detect(Some("es")), Locale::Spanish);
+        assert_eq!(Locale::detect(Some("de")), Locale::English);
+
+        for (var, value) in vars.iter().zip(saved) {
+            match value {
+                Some(value) => env::set_var(var, value),
+                None => env::remove_var(var),
+            }
+        }
+    }
+
+    #[test]
+    fn tr_falls_back_to_english() {
+        assert_eq!(Locale::Spanish.tr("Movies"), "Películas");
+        assert_eq!(Locale::English.tr("Movies"), "Movies");
+        assert_eq!(Locale::Spanish.tr("Not in the catalog"), "Not in the catalog");
+    }
+
+    /// Sources whose `tr` and `fill` calls are checked against the catalog.
+    const SOURCES: [(&str, &str); 10] = [
+        ("anniversary.rs", include_str!("anniversary.rs")),
+        ("app.rs", include_str!("app.rs")),
+        ("cli.rs", include_str!("cli.rs")),
+        ("data.rs", include_str!("data.rs")),
+        ("goto.rs", include_str!("goto.rs")),
+        ("palette.rs", include_str!("palette.rs")),
+        ("quiz.rs", include_str!("quiz.rs")),
+        ("random.rs", include_str!("random.rs")),
+        ("timeline.rs", include_str!("timeline.rs")),
+        ("ui.rs", include_str!("ui.rs")),
+    ];
+
+    /// Sources whose `match` arms give display labels that are translated
+    /// later, e.g. the mode names of the status bar.
+    const LABEL_SOURCES: [(&str, &str); 3] = [
+        ("ui.rs", include_str!("ui.rs")),
+        ("facets.rs", include_str!("facets.rs")),
+        ("family.rs", include_str!("family.rs")),
+    ];
+
+    /// Splits `source` into its code, without comments and with every
+    /// string literal replaced by `"N"`, and the unescaped literals.
+    fn mask_literals(source: &str) -> (String, Vec<String>) {
+        let mut code = String::new();
+        let mut literals = Vec::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '/' if chars.peek() == Some(&'/') => {
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            code.push('\n');
+                            break;
+                        }
+                    }
+                }
+                '"' => {
+                    let mut literal = String::new();
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '"' => break,
+                            '\\' => match chars.next() {
+                                Some('n') => literal.push('\n'),
+                                Some(escaped) => literal.push(escaped),
+                                None => break,
+                            },
+                            c => literal.push(c),
+                        }
+                    }
+                    code.push_str(&format!("\"{}\"", literals.len()));
+                    literals.push(literal);
+                }
+                c => code.push(c),
+            }
+        }
+        (code, literals)
+    }
+
+    /// The literals quoted as `"N"` in `code`.
+    fn quoted<'a>(code: &str, literals: &'a [String]) -> Vec<&'a str> {
+        code.split('"')
+            .skip(1)
+            .step_by(2)
+            .filter_map(|index| literals.get(index.parse::<usize>().ok()?))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The text from `start` up to the parenthesis closing the one just
+    /// before it.
+    fn call_arguments(code: &str, start: usize) -> &str {
+        let mut depth = 1;
+        for (offset, c) in code[start..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return &code[start..start + offset];
+                    }
+                }
+                _ => {}
+            }
+        }
+        &code[start..]
+    }
+
+    /// Every text the interface looks up in the catalog: the literals given
+    /// to `tr` (all of them, for `tr(if ...)` and `tr(match ...)`) and the
+    /// template of `fill`.
+    fn looked_up_texts() -> Vec<(String, String)> {
+        let mut texts = Vec::new();
+        for (file, source) in SOURCES {
+            let (code, literals) = mask_literals(source);
+            for (call, all_literals) in [(".tr(", true), (".fill(", false)] {
+                for (position, _) in code.match_indices(call) {
+                    let arguments = call_arguments(&code, position + call.len());
+                    let keys = quoted(arguments, &literals);
+                    let keys = if all_literals {
+                        keys
+                    } else if arguments.starts_with('"') {
+                        keys.into_iter().take(1).collect()
+                    } else {
+                        Vec::new()
+                    };
+                    texts.extend(keys.into_iter().map(|key| (file.to_string(), key.to_string())));
+                }
+            }
+        }
+        for (file, source) in LABEL_SOURCES {
+            let (code, literals) = mask_literals(source);
+            for arm in code.split("=> ").skip(1) {
+                let arm = arm.strip_prefix('(').unwrap_or(arm);
+                if arm.starts_with('"') {
+                    texts.extend(quoted(arm, &literals).first().map(|key| (file.to_string(), key.to_string())));
+                }
+            }
+        }
+        texts
+    }
+
+    #[test]
+    fn catalog_covers_every_text() {
+        use crate::app::TAB_TITLES;
+        use crate::keymap::Action;
+        use crate::stats::STATS_PAGES;
+
+        let mut texts = looked_up_texts();
+        for &action in Action::ALL {
+            for text in [action.section(), action.description(), action.hint()] {
+                texts.push(("keymap.rs".to_string(), text.to_string()));
+            }
+        }
+        texts.extend(TAB_TITLES.iter().chain(STATS_PAGES.iter()).map(|text| ("app.rs".to_string(), text.to_string())));
+
+        let missing: Vec<String> = texts.iter()
+            .filter(|(_, text)| text.chars().any(|c| c.is_ascii_alphabetic()))
+            .filter(|(_, text)| spanish(text).is_none())
+            .map(|(file, text)| format!("{}: {:?}", file, text))
+            .collect();
+        assert!(missing.is_empty(), "No Spanish for:\n{}", missing.join("\n"));
+        assert!(texts.len() > 300, "Only found {} texts; is the scan broken?", texts.len());
+    }
+
+    #[test]
+    fn fill_replaces_placeholders_in_order() {
+        assert_eq!(Locale::English.fill(", around episodes {}-{}", &[&107, &108]), ", around episodes 107-108");
+        assert_eq!(Locale::Spanish.fill(", around episodes {}-{}", &[&107, &108]), ", en torno a los episodios 107-108");
+        assert_eq!(Locale::English.fill("{} and {}", &[&"Goku"]), "Goku and ");
+        assert_eq!(Locale::English.fill("No placeholders", &[&1]), "No placeholders");
+    }
+}
@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+use std::fmt;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    NextTab,
+    PrevSeries,
+    NextSeries,
+    Up,
+    Down,
+    Select,
+    Back,
+    Forward,
+    Quit,
+    Help,
+    Search,
+    CommandPalette,
+    ToggleWatched,
+    ToggleFavorite,
+    EditNote,
+    Rate,
+    Random,
+    RandomFilter,
+    Quiz,
+    FamilyTree,
+    Compare,
+    Filter,
+    CycleTheme,
+    SortMethod,
+    SortOrder,
+    Top,
+    Bottom,
+    HalfPageUp,
+    HalfPageDown,
+    PageUp,
+    PageDown,
+    GoTo,
+    Zoom,
+    FindInList,
+    FindNext,
+    FindPrev,
+}
+
+/// The built-in set of bindings that `[keys]` overrides are applied on top of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeymapPreset {
+    #[default]
+    Default,
+    Vim,
+}
+
+impl Action {
+    /// Every action, in the order it is listed on the help screen.
+    pub const ALL: &'static [Action] = &[
+        Action::NextTab,
+        Action::PrevSeries,
+        Action::NextSeries,
+        Action::Up,
+        Action::Down,
+        Action::Select,
+        Action::Back,
+        Action::Forward,
+        Action::Top,
+        Action::Bottom,
+        Action::HalfPageUp,
+        Action::HalfPageDown,
+        Action::PageUp,
+        Action::PageDown,
+        Action::GoTo,
+        Action::Zoom,
+        Action::FindInList,
+        Action::FindNext,
+        Action::FindPrev,
+        Action::Quit,
+        Action::Help,
+        Action::Search,
+        Action::CommandPalette,
+        Action::ToggleWatched,
+        Action::ToggleFavorite,
+        Action::EditNote,
+        Action::Rate,
+        Action::Random,
+        Action::RandomFilter,
+        Action::Quiz,
+        Action::FamilyTree,
+        Action::Compare,
+        Action::Filter,
+        Action::CycleTheme,
+        Action::SortMethod,
+        Action::SortOrder,
+    ];
+
+    pub fn section(self) -> &'static str {
+        match self {
+            Action::NextTab
+            | Action::PrevSeries
+            | Action::NextSeries
+            | Action::Up
+            | Action::Down
+            | Action::Select
+            | Action::Back
+            | Action::Forward
+            | Action::Top
+            | Action::Bottom
+            | Action::HalfPageUp
+            | Action::HalfPageDown
+            | Action::PageUp
+            | Action::PageDown
+            | Action::GoTo
+            | Action::Zoom => "Navigation",
+            Action::FindInList | Action::FindNext | Action::FindPrev => "Find in list",
+            Action::Quit
+            | Action::Help
+            | Action::Search
+            | Action::CommandPalette
+            | Action::ToggleWatched
+            | Action::ToggleFavorite
+            | Action::EditNote
+            | Action::Rate
+            | Action::Random
+            | Action::RandomFilter
+            | Action::Quiz
+            | Action::FamilyTree
+            | Action::Compare
+            | Action::Filter
+            | Action::CycleTheme => "Actions",
+            Action::SortMethod | Action::SortOrder => "Sorting",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::NextTab => "Switch between main tabs",
+            Action::PrevSeries => "Previous series (in Episodes tab)",
+            Action::NextSeries => "Next series (in Episodes tab)",
+            Action::Up => "Move up in lists",
+            Action::Down => "Move down in lists",
+            Action::Select => "View details of selected item",
+            Action::Back => "Go back to the previous view",
+            Action::Forward => "Go forward again after going back",
+            Action::Top => "Jump to first item (or to item N with a count)",
+            Action::Bottom => "Jump to last item (or to item N with a count)",
+            Action::HalfPageUp => "Scroll up half a page",
+            Action::HalfPageDown => "Scroll down half a page",
+            Action::PageUp => "Scroll up a page",
+            Action::PageDown => "Scroll down a page",
+            Action::GoTo => "Go to an episode by number (42, dbz 150, #300)",
+            Action::Zoom => "Zoom the timeline between years and months",
+            Action::FindInList => "Find a title in the current list",
+            Action::FindNext => "Jump to next match",
+            Action::FindPrev => "Jump to previous match",
+            Action::Quit => "Quit the application",
+            Action::Help => "Toggle this help screen",
+            Action::Search => "Enter search mode",
+            Action::CommandPalette => "Open the command palette",
+            Action::ToggleWatched => "Mark selected episode or movie as (not) watched",
+            Action::ToggleFavorite => "Add selected item to (or remove it from) favorites",
+            Action::EditNote => "Write a note about selected item",
+            Action::Rate => "Rate selected episode or movie from 1 to 10",
+            Action::Random => "Open a random episode or movie (again to re-roll)",
+            Action::RandomFilter => "Choose what random picks can land on",
+            Action::Quiz => "Start a trivia quiz",
+            Action::FamilyTree => "Show the family tree of selected character",
+            Action::Compare => "Compare selected character with another one",
+            Action::Filter => "Filter the characters or movies list",
+            Action::CycleTheme => "Switch to the next color theme",
+            Action::SortMethod => "Change sort method",
+            Action::SortOrder => "Toggle sort order",
+        }
+    }
+
+    /// Short label used for key hints in the status bar.
+    pub fn hint(self) -> &'static str {
+        match self {
+            Action::NextTab => "tab",
+            Action::PrevSeries | Action::NextSeries => "series",
+            Action::Up | Action::Down => "move",
+            Action::Select => "open",
+            Action::Back => "back",
+            Action::Forward => "forward",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::HalfPageUp | Action::HalfPageDown => "scroll",
+            Action::PageUp | Action::PageDown => "page",
+            Action::GoTo => "go to",
+            Action::Zoom => "zoom",
+            Action::FindInList => "find",
+            Action::FindNext | Action::FindPrev => "next match",
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::Search => "search",
+            Action::CommandPalette => "commands",
+            Action::ToggleWatched => "watched",
+            Action::ToggleFavorite => "star",
+            Action::EditNote => "note",
+            Action::Rate => "rate",
+            Action::Random => "re-roll",
+            Action::RandomFilter => "filters",
+            Action::Quiz => "quiz",
+            Action::FamilyTree => "family",
+            Action::Compare => "compare",
+            Action::Filter => "filter",
+            Action::CycleTheme => "theme",
+            Action::SortMethod => "sort",
+            Action::SortOrder => "order",
+        }
+    }
+
+    fn default_keys(self, preset: KeymapPreset) -> &'static [&'static str] {
+        if preset == KeymapPreset::Vim {
+            return self.vim_keys();
+        }
+        match self {
+            Action::NextTab => &["Tab"],
+            Action::PrevSeries => &["Left"],
+            Action::NextSeries => &["Right"],
+            Action::Up => &["Up"],
+            Action::Down => &["Down"],
+            Action::Select => &["Enter"],
+            Action::Back => &["Esc", "Alt-Left"],
+            Action::Forward => &["Alt-Right"],
+            Action::Quit => &["q"],
+            Action::Help => &["h"],
+            Action::Search => &["s"],
+            Action::GoTo => &["g", "Ctrl-g"],
+            Action::Zoom => &["z"],
+            Action::CommandPalette => &["Ctrl-p"],
+            Action::ToggleWatched => &["w"],
+            Action::ToggleFavorite => &["f"],
+            Action::EditNote => &["e"],
+            Action::Rate => &["r"],
+            Action::Random => &["x"],
+            Action::RandomFilter => &["X"],
+            Action::Quiz => &["Q"],
+            Action::FamilyTree => &["F"],
+            Action::Compare => &["c"],
+            Action::Filter => &["v"],
+            Action::CycleTheme => &["t"],
+            Action::SortMethod => &["m"],
+            Action::SortOrder => &["o"],
+            Action::Top => &["Home"],
+            Action::Bottom => &["End"],
+            Action::PageUp => &["PageUp"],
+            Action::PageDown => &["PageDown"],
+            Action::HalfPageUp
+            | Action::HalfPageDown
+            | Action::FindInList
+            | Action::FindNext
+            | Action::FindPrev => &[],
+        }
+    }
+
+    fn vim_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::NextTab => &["Tab"],
+            Action::PrevSeries => &["h", "Left"],
+            Action::NextSeries => &["l", "Right"],
+            Action::Up => &["k", "Up"],
+            Action::Down => &["j", "Down"],
+            Action::Select => &["Enter"],
+            Action::Back => &["Esc", "Ctrl-o"],
+            Action::Forward => &["Alt-Right"],
+            Action::Top => &["g", "Home"],
+            Action::Bottom => &["G", "End"],
+            Action::HalfPageUp => &["Ctrl-u"],
+            Action::HalfPageDown => &["Ctrl-d"],
+            Action::PageUp => &["Ctrl-b", "PageUp"],
+            Action::PageDown => &["Ctrl-f", "PageDown"],
+            Action::GoTo => &["Ctrl-g"],
+            Action::Zoom => &["z"],
+            Action::FindInList => &["/"],
+            Action::FindNext => &["n"],
+            Action::FindPrev => &["N"],
+            Action::Quit => &["q"],
+            Action::Help => &["?"],
+            Action::Search => &["s"],
+            Action::CommandPalette => &["Ctrl-p", ":"],
+            Action::ToggleWatched => &["w"],
+            Action::ToggleFavorite => &["f"],
+            Action::EditNote => &["e"],
+            Action::Rate => &["r"],
+            Action::Random => &["x"],
+            Action::RandomFilter => &["X"],
+            Action::Quiz => &["Q"],
+            Action::FamilyTree => &["F"],
+            Action::Compare => &["c"],
+            Action::Filter => &["v"],
+            Action::CycleTheme => &["t"],
+            Action::SortMethod => &["m"],
+            Action::SortOrder => &["o"],
+        }
+    }
+}
+
+/// A single key press, normalized so that the shift state of a character
+/// is carried by the character itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        loop {
+            if let Some(r) = rest.strip_prefix("Ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("Alt-") {
+                modifiers |= KeyModifiers::ALT;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+
+        let code = match rest {
+            "Tab" => KeyCode::Tab,
+            "BackTab" => KeyCode::BackTab,
+            "Enter" => KeyCode::Enter,
+            "Esc" => KeyCode::Esc,
+            "Backspace" => KeyCode::Backspace,
+            "Delete" => KeyCode::Delete,
+            "Insert" => KeyCode::Insert,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "Space" => KeyCode::Char(' '),
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match rest.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => return Err(format!("Unknown key: {}", s)),
+                    },
+                }
+            }
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl From<KeyEvent> for KeyBinding {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+pub struct KeyMap {
+    bindings: HashMap<KeyBinding, Action>,
+    /// Keys of each action in the order they were configured, so that the
+    /// first one can be shown as the primary key.
+    keys: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl KeyMap {
+    /// Builds the keymap from the bindings of `preset`, replacing the keys of
+    /// every action listed in `overrides`. Fails if a key ends up bound to
+    /// more than one action.
+    pub fn new(preset: KeymapPreset, overrides: &HashMap<Action, Vec<String>>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut bindings = HashMap::new();
+        let mut action_keys: HashMap<Action, Vec<KeyBinding>> = HashMap::new();
+        let mut conflicts = Vec::new();
+
+        for &action in Action::ALL {
+            let keys: Vec<&str> = match overrides.get(&action) {
+                Some(keys) => keys.iter().map(String::as_str).collect(),
+                None => action.default_keys(preset).to_vec(),
+            };
+            for key in keys {
+                let binding = KeyBinding::parse(key)?;
+                action_keys.entry(action).or_default().push(binding);
+                if let Some(existing) = bindings.insert(binding, action) {
+                    if existing != action {
+                        conflicts.push(format!("'{}' is bound to both {:?} and {:?}", binding, existing, action));
+                    }
+                }
+            }
+        }
+
+        if !conflicts.is_empty() {
+            return Err(From::from(format!("Conflicting key bindings: {}", conflicts.join("; "))));
+        }
+
+        Ok(Self { bindings, keys: action_keys })
+    }
+
+    /// Looks up the action for a key press. Characters fall back to their
+    /// lowercase binding so that e.g. `Q` quits when only `q` is bound.
+    pub fn action_for(&self, key: KeyEvent) -> Option<Action> {
+        let binding = KeyBinding::from(key);
+        if let Some(action) = self.bindings.get(&binding) {
+            return Some(*action);
+        }
+        match binding.code {
+            KeyCode::Char(c) if c.is_uppercase() => {
+                let lower = c.to_lowercase().next().unwrap_or(c);
+                self.bindings.get(&KeyBinding::new(KeyCode::Char(lower), binding.modifiers)).copied()
+            }
+            _ => None,
+        }
+    }
+
+    pub fn keys_for(&self, action: Action) -> &[KeyBinding] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Human readable list of the keys bound to `action`, e.g. "Up/k".
+    pub fn describe(&self, action: Action) -> String {
+        self.keys_for(action).iter()
+            .map(|k| k.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
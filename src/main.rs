@@ -1,9 +1,25 @@
 mod ui;
+mod anniversary;
 mod app;
 mod handlers;
 mod data;
+mod facets;
+mod family;
+mod cli;
+mod config;
+mod goto;
+mod i18n;
+mod keymap;
+mod palette;
+mod quiz;
+mod random;
+mod stats;
+mod theme;
+mod timeline;
+mod user_data;
 
 use std::io;
+use std::time::Duration;
 use crossterm::{
     event::{self, Event},
     terminal::{self, ClearType},
@@ -19,6 +35,16 @@ use handlers::handle_key_event;
 use ui::draw_ui;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Subcommands print their answer and exit without starting the interface
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return cli::run(&args);
+    }
+
+    // Initialize app state before touching the terminal so that data and
+    // config errors are printed normally
+    let mut app = App::new()?;
+
     // Setup terminal
     terminal::enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -28,13 +54,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Initialize app state
-    let mut app = App::new()?;
-
     // Main loop
     loop {
         terminal.draw(|f| draw_ui(f, &mut app))?;
 
+        // Wake up periodically so that transient status messages expire
+        if !event::poll(Duration::from_millis(500))? {
+            continue;
+        }
         if let Event::Key(key) = event::read()? {
             if !handle_key_event(key, &mut app)? {
                 break;
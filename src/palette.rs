@@ -0,0 +1,149 @@
+use tui::widgets::ListState;
+use crate::app::{App, EpisodeSortMethod, MovieSortMethod, TAB_TITLES};
+use crate::keymap::Action;
+use crate::stats::STATS_PAGES;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Action(Action),
+    SwitchTab(usize),
+    SortEpisodes(EpisodeSortMethod),
+    SortMovies(MovieSortMethod),
+    JumpToSeries(usize),
+    OpenSaga(usize, String),
+    SetTheme(usize),
+    ShowStats(usize),
+    ShowOnThisDay,
+    Export,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub command: Command,
+}
+
+/// State of the open command palette.
+pub struct Palette {
+    pub query: String,
+    pub entries: Vec<PaletteEntry>,
+    /// Indices into `entries` matching `query`, best match first.
+    pub matches: Vec<usize>,
+    pub list_state: ListState,
+}
+
+impl Palette {
+    pub fn new(app: &App) -> Self {
+        let entries = build_entries(app);
+        let mut palette = Self {
+            query: String::new(),
+            matches: Vec::new(),
+            entries,
+            list_state: ListState::default(),
+        };
+        palette.refilter();
+        palette
+    }
+
+    pub fn refilter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self.entries.iter().enumerate()
+            .filter_map(|(index, entry)| fuzzy_score(&self.query, &entry.label).map(|score| (score, index)))
+            .collect();
+        // Stable sort keeps the natural order between equal scores
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.list_state.select(if self.matches.is_empty() { None } else { Some(0) });
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let len = self.matches.len() as isize;
+        let selected = self.list_state.selected().unwrap_or(0) as isize;
+        self.list_state.select(Some((selected + delta).rem_euclid(len) as usize));
+    }
+
+    pub fn selected_command(&self) -> Option<Command> {
+        let index = *self.matches.get(self.list_state.selected()?)?;
+        Some(self.entries[index].command.clone())
+    }
+}
+
+fn build_entries(app: &App) -> Vec<PaletteEntry> {
+    let locale = app.locale;
+    let mut entries = Vec::new();
+    let mut push = |label: String, command: Command| entries.push(PaletteEntry { label, command });
+
+    for (tab, name) in TAB_TITLES.iter().enumerate() {
+        push(locale.fill("Switch to {} tab", &[&locale.tr(name)]), Command::SwitchTab(tab));
+    }
+    for (series_index, series) in app.guide.iter().enumerate() {
+        push(locale.fill("Jump to series: {}", &[&series.series]), Command::JumpToSeries(series_index));
+    }
+    for (method, name) in [
+        (EpisodeSortMethod::EpisodeNumber, locale.tr("episode number")),
+        (EpisodeSortMethod::Title, locale.tr("title")),
+        (EpisodeSortMethod::ReleaseDate, locale.tr("release date")),
+        (EpisodeSortMethod::Rating, locale.tr("rating")),
+    ] {
+        push(locale.fill("Sort episodes by {}", &[&name]), Command::SortEpisodes(method));
+    }
+    for (method, name) in [
+        (MovieSortMethod::Number, locale.tr("number")),
+        (MovieSortMethod::Title, locale.tr("title")),
+        (MovieSortMethod::ReleaseDate, locale.tr("release date")),
+        (MovieSortMethod::Rating, locale.tr("rating")),
+    ] {
+        push(locale.fill("Sort movies by {}", &[&name]), Command::SortMovies(method));
+    }
+    for (series_index, series) in app.guide.iter().enumerate() {
+        for saga in series.sagas() {
+            push(locale.fill("Open saga: {}", &[&saga]), Command::OpenSaga(series_index, saga.to_string()));
+        }
+    }
+    for (theme_index, theme) in app.themes.iter().enumerate() {
+        push(locale.fill("Use theme: {}", &[&theme.name]), Command::SetTheme(theme_index));
+    }
+    for (page, name) in STATS_PAGES.iter().enumerate() {
+        push(locale.fill("Show stats: {}", &[&locale.tr(name)]), Command::ShowStats(page));
+    }
+    push(locale.tr("Show on this day").to_string(), Command::ShowOnThisDay);
+    push(locale.tr("Export episodes, movies and favorites").to_string(), Command::Export);
+    for &action in Action::ALL {
+        if action != Action::CommandPalette {
+            push(locale.tr(action.description()).to_string(), Command::Action(action));
+        }
+    }
+
+    entries
+}
+
+/// Scores `label` against `query` as a case-insensitive subsequence match,
+/// rewarding consecutive characters and matches at word starts. Returns
+/// `None` if some query character is missing.
+pub fn fuzzy_score(query: &str, label: &str) -> Option<i64> {
+    if query.trim().is_empty() {
+        return Some(0);
+    }
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (position..label.len()).find(|&i| label[i] == query_char)?;
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !label[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(found);
+        position = found + 1;
+    }
+
+    // Prefer shorter labels among equally good matches
+    Some(score * 100 - label.len() as i64)
+}
@@ -0,0 +1,189 @@
+use chrono::Datelike;
+use crate::data::{Character, Movie, Series, parse_release_date};
+use crate::i18n::Locale;
+
+/// Number of choices offered for every question.
+const CHOICES: usize = 4;
+
+/// A multiple-choice question about the loaded data.
+#[derive(Debug, Clone)]
+pub struct Question {
+    pub text: String,
+    pub choices: Vec<String>,
+    /// Index into `choices` of the right answer.
+    pub answer: usize,
+    /// Shown once the question has been answered.
+    pub explanation: Option<String>,
+}
+
+/// State of a quiz session.
+#[derive(Debug, Clone)]
+pub struct Quiz {
+    pub question: Question,
+    /// Highlighted choice.
+    pub selected: usize,
+    /// Choice given for the current question, once answered.
+    pub given: Option<usize>,
+    pub correct: u32,
+    pub answered: u32,
+}
+
+impl Quiz {
+    pub fn new(question: Question) -> Self {
+        Self { question, selected: 0, given: None, correct: 0, answered: 0 }
+    }
+
+    /// Answers the current question with `choice`. Returns whether it was
+    /// right; answering twice changes nothing.
+    pub fn answer(&mut self, choice: usize) -> bool {
+        if self.given.is_none() && choice < self.question.choices.len() {
+            self.given = Some(choice);
+            self.answered += 1;
+            if choice == self.question.answer {
+                self.correct += 1;
+            }
+        }
+        self.given == Some(self.question.answer)
+    }
+
+    pub fn next_question(&mut self, question: Question) {
+        self.question = question;
+        self.selected = 0;
+        self.given = None;
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.question.choices.len() as isize - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, last.max(0)) as usize;
+    }
+}
+
+/// Makes up one kind of question, if the data allows.
+type Generator = fn(&[Series], &[Movie], &[Character], Locale) -> Option<Question>;
+
+/// Makes up a random question, worded in `locale`, or `None` if the data is
+/// too thin for any.
+pub fn generate_question(guide: &[Series], movies: &[Movie], characters: &[Character], locale: Locale) -> Option<Question> {
+    let generators: [Generator; 6] = [
+        episode_saga,
+        movie_year,
+        movie_director,
+        movie_trivia,
+        character_alias,
+        character_race,
+    ];
+    // Some generators need more data than others; give each a few tries
+    for _ in 0..generators.len() * 4 {
+        let generate = generators[fastrand::usize(..generators.len())];
+        if let Some(question) = generate(guide, movies, characters, locale) {
+            return Some(question);
+        }
+    }
+    None
+}
+
+/// Shuffles `answer` in with the first three wrong answers of `pool`, which
+/// callers order by how good a decoy they make.
+fn with_choices(text: String, answer: String, pool: Vec<String>, explanation: Option<String>) -> Option<Question> {
+    let mut choices: Vec<String> = Vec::new();
+    for choice in pool {
+        if choice != answer && !choices.contains(&choice) {
+            choices.push(choice);
+        }
+    }
+    if choices.len() < CHOICES - 1 {
+        return None;
+    }
+    choices.truncate(CHOICES - 1);
+    choices.push(answer.clone());
+    fastrand::shuffle(&mut choices);
+    let answer = choices.iter().position(|choice| *choice == answer)?;
+    Some(Question { text, choices, answer, explanation })
+}
+
+fn shuffled(mut pool: Vec<String>) -> Vec<String> {
+    fastrand::shuffle(&mut pool);
+    pool
+}
+
+fn pick<T>(items: &[T]) -> Option<&T> {
+    if items.is_empty() {
+        None
+    } else {
+        Some(&items[fastrand::usize(..items.len())])
+    }
+}
+
+fn episode_saga(guide: &[Series], _: &[Movie], _: &[Character], locale: Locale) -> Option<Question> {
+    let series = pick(guide)?;
+    let episode = pick(&series.episodes)?;
+    // Sagas of the same series make the better decoys
+    let mut pool = shuffled(series.sagas().into_iter().map(str::to_string).collect());
+    pool.extend(shuffled(guide.iter().flat_map(|series| series.sagas()).map(str::to_string).collect()));
+    with_choices(
+        locale.fill("Which saga is {} episode {}, \"{}\", part of?", &[&series.series, &episode.episode_number, &episode.title]),
+        episode.saga.clone(),
+        pool,
+        Some(episode.description.clone()),
+    )
+}
+
+fn movie_year(_: &[Series], movies: &[Movie], _: &[Character], locale: Locale) -> Option<Question> {
+    let movie = pick(movies)?;
+    let year = parse_release_date(&movie.release_date)?.year();
+    with_choices(
+        locale.fill("In what year was \"{}\" released?", &[&movie.title]),
+        year.to_string(),
+        shuffled((year - 4..=year + 4).map(|year| year.to_string()).collect()),
+        Some(movie.trivia.clone()),
+    )
+}
+
+fn movie_director(_: &[Series], movies: &[Movie], _: &[Character], locale: Locale) -> Option<Question> {
+    let movie = pick(movies)?;
+    with_choices(
+        locale.fill("Who directed \"{}\"?", &[&movie.title]),
+        movie.director.clone(),
+        shuffled(movies.iter().map(|movie| movie.director.clone()).collect()),
+        Some(movie.trivia.clone()),
+    )
+}
+
+fn movie_trivia(_: &[Series], movies: &[Movie], _: &[Character], locale: Locale) -> Option<Question> {
+    let movie = pick(movies)?;
+    if movie.trivia.is_empty() {
+        return None;
+    }
+    with_choices(
+        locale.fill("Which movie is this about? \"{}\"", &[&movie.trivia]),
+        movie.title.clone(),
+        shuffled(movies.iter().map(|movie| movie.title.clone()).collect()),
+        Some(movie.description.clone()),
+    )
+}
+
+fn character_alias(_: &[Series], _: &[Movie], characters: &[Character], locale: Locale) -> Option<Question> {
+    let character = pick(characters)?;
+    // "Prince Vegeta" would give the answer away
+    let aliases: Vec<&String> = character.aliases.iter().filter(|alias| !alias.contains(&character.name)).collect();
+    let alias = pick(&aliases)?;
+    with_choices(
+        locale.fill("Which character is also known as {}?", &[alias]),
+        character.name.clone(),
+        shuffled(characters.iter().map(|character| character.name.clone()).collect()),
+        Some(character.description.clone()),
+    )
+}
+
+fn character_race(_: &[Series], _: &[Movie], characters: &[Character], locale: Locale) -> Option<Question> {
+    let character = pick(characters)?;
+    if character.race == "Unknown" || character.race == character.name {
+        return None;
+    }
+    with_choices(
+        locale.fill("What race is {}?", &[&character.name]),
+        character.race.clone(),
+        shuffled(characters.iter().map(|character| character.race.clone()).filter(|race| race != "Unknown").collect()),
+        Some(character.description.clone()),
+    )
+}
@@ -0,0 +1,193 @@
+use chrono::Datelike;
+use crate::app::SearchResultType;
+use crate::data::{Movie, Series, parse_duration_minutes, parse_release_date};
+use crate::goto::{find_series, series_aliases};
+use crate::i18n::Locale;
+use crate::user_data::{UserData, episode_key, movie_key};
+
+/// Which episodes and movies a random pick may land on. Every field left
+/// empty lets everything through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RandomFilter {
+    /// Only episodes of this series.
+    pub series: Option<usize>,
+    /// Only episodes of sagas whose name contains this, lowercased.
+    pub saga: Option<String>,
+    pub from_year: Option<i32>,
+    pub to_year: Option<i32>,
+    pub max_minutes: Option<u32>,
+    pub unwatched_only: bool,
+    pub canon_only: bool,
+    pub episodes_only: bool,
+    pub movies_only: bool,
+}
+
+/// Parses filters written as space separated words:
+///
+/// - `dbz`, `gt`, `super` keep episodes of that series
+/// - `saga:frieza` keeps episodes of sagas with "frieza" in their name
+/// - `1989`, `1989-1995`, `1995-` or `-1990` keep releases of those years
+/// - `max:30m`, `max:1h 30m` keep what runs at most that long
+/// - `unwatched`, `canon`, `episodes` and `movies` do what they say
+pub fn parse_filter(input: &str, guide: &[Series], locale: Locale) -> Result<RandomFilter, String> {
+    let mut filter = RandomFilter::default();
+    let mut words = input.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        let lower = word.to_lowercase();
+        if let Some(saga) = lower.strip_prefix("saga:") {
+            if saga.is_empty() {
+                return Err(locale.tr("saga: needs part of a saga name").to_string());
+            }
+            filter.saga = Some(saga.to_string());
+        } else if let Some(duration) = lower.strip_prefix("max:") {
+            // "max:1h 30m" spans two words
+            let mut duration = duration.to_string();
+            if let Some(minutes) = words.peek().filter(|next| next.ends_with('m') && next[..next.len() - 1].parse::<u32>().is_ok()) {
+                duration = format!("{} {}", duration, minutes);
+                words.next();
+            }
+            filter.max_minutes = Some(parse_duration_minutes(&duration).ok_or_else(|| locale.fill("Not a duration: {}", &[&duration]))?);
+        } else if lower.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+            let (from, to) = parse_years(&lower, locale)?;
+            filter.from_year = from;
+            filter.to_year = to;
+        } else {
+            match lower.as_str() {
+                "unwatched" => filter.unwatched_only = true,
+                "canon" => filter.canon_only = true,
+                "episodes" => filter.episodes_only = true,
+                "movies" => filter.movies_only = true,
+                series => filter.series = Some(find_series(guide, series, locale)?),
+            }
+        }
+    }
+    if filter.movies_only && filter.episodes_only {
+        return Err(locale.tr("Pick either episodes or movies").to_string());
+    }
+    if filter.movies_only && (filter.series.is_some() || filter.saga.is_some()) {
+        return Err(locale.tr("Movies have no series or saga to filter by").to_string());
+    }
+    Ok(filter)
+}
+
+fn parse_years(range: &str, locale: Locale) -> Result<(Option<i32>, Option<i32>), String> {
+    let year = |s: &str| -> Result<Option<i32>, String> {
+        if s.is_empty() {
+            Ok(None)
+        } else {
+            s.parse().map(Some).map_err(|_| locale.fill("Not a year: {}", &[&s]))
+        }
+    };
+    let (from, to) = match range.split_once('-') {
+        Some((from, to)) => (year(from)?, year(to)?),
+        None => (year(range)?, year(range)?),
+    };
+    match (from, to) {
+        (None, None) => Err(locale.fill("Not a year range: {}", &[&range])),
+        (Some(from), Some(to)) if from > to => Err(locale.fill("Years go the wrong way: {}", &[&range])),
+        years => Ok(years),
+    }
+}
+
+impl RandomFilter {
+    /// The filter written back in the form `parse_filter` reads, with the
+    /// series by its short name.
+    pub fn describe(&self, guide: &[Series]) -> String {
+        let mut words: Vec<String> = Vec::new();
+        if self.episodes_only {
+            words.push("episodes".to_string());
+        }
+        if self.movies_only {
+            words.push("movies".to_string());
+        }
+        if let Some(series) = self.series.and_then(|index| guide.get(index)) {
+            let aliases = series_aliases(&series.series);
+            words.push(aliases.get(2).unwrap_or(&aliases[0]).clone());
+        }
+        if let Some(saga) = &self.saga {
+            words.push(format!("saga:{}", saga));
+        }
+        match (self.from_year, self.to_year) {
+            (Some(from), Some(to)) if from == to => words.push(from.to_string()),
+            (Some(from), Some(to)) => words.push(format!("{}-{}", from, to)),
+            (Some(from), None) => words.push(format!("{}-", from)),
+            (None, Some(to)) => words.push(format!("-{}", to)),
+            (None, None) => {}
+        }
+        if let Some(minutes) = self.max_minutes {
+            words.push(format!("max:{}m", minutes));
+        }
+        if self.unwatched_only {
+            words.push("unwatched".to_string());
+        }
+        if self.canon_only {
+            words.push("canon".to_string());
+        }
+        words.join(" ")
+    }
+
+    fn years_match(&self, release_date: &str) -> bool {
+        if self.from_year.is_none() && self.to_year.is_none() {
+            return true;
+        }
+        match parse_release_date(release_date) {
+            Some(date) => {
+                self.from_year.is_none_or(|from| date.year() >= from)
+                    && self.to_year.is_none_or(|to| date.year() <= to)
+            }
+            None => false,
+        }
+    }
+
+    fn duration_matches(&self, duration: &str) -> bool {
+        match self.max_minutes {
+            Some(max) => parse_duration_minutes(duration).is_some_and(|minutes| minutes <= max),
+            None => true,
+        }
+    }
+}
+
+/// Every episode and movie the filter lets through.
+pub fn candidates(filter: &RandomFilter, guide: &[Series], movies: &[Movie], user_data: &UserData) -> Vec<SearchResultType> {
+    let mut candidates = Vec::new();
+    if !filter.movies_only {
+        for (series_index, series) in guide.iter().enumerate() {
+            if filter.series.is_some_and(|index| index != series_index) {
+                continue;
+            }
+            for (episode_index, episode) in series.episodes.iter().enumerate() {
+                let matches = filter.saga.as_ref().is_none_or(|saga| episode.saga.to_lowercase().contains(saga.as_str()))
+                    && filter.years_match(&episode.release_date)
+                    && filter.duration_matches(&episode.duration)
+                    && !(filter.canon_only && episode.filler)
+                    && !(filter.unwatched_only && user_data.is_watched(&episode_key(&series.series, episode.episode_number)));
+                if matches {
+                    candidates.push(SearchResultType::Episode(series_index, episode_index));
+                }
+            }
+        }
+    }
+    if !filter.episodes_only && filter.series.is_none() && filter.saga.is_none() {
+        for (movie_index, movie) in movies.iter().enumerate() {
+            let matches = filter.years_match(&movie.release_date)
+                && filter.duration_matches(&movie.runtime)
+                && (movie.canon || !filter.canon_only)
+                && !(filter.unwatched_only && user_data.is_watched(&movie_key(movie.number)));
+            if matches {
+                candidates.push(SearchResultType::Movie(movie_index));
+            }
+        }
+    }
+    candidates
+}
+
+/// Picks one of `candidates`, avoiding `previous` when there is a choice so
+/// that a re-roll always lands somewhere new.
+pub fn pick(candidates: &[SearchResultType], previous: Option<&SearchResultType>) -> Option<SearchResultType> {
+    let choices: Vec<&SearchResultType> = candidates.iter().filter(|candidate| Some(*candidate) != previous).collect();
+    let choices = if choices.is_empty() { candidates.iter().collect() } else { choices };
+    if choices.is_empty() {
+        return None;
+    }
+    Some(choices[fastrand::usize(..choices.len())].clone())
+}
@@ -0,0 +1,85 @@
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+use crate::data::{Character, Movie, Series, parse_duration_minutes, parse_release_date};
+use crate::user_data::UserData;
+
+/// Pages of the Stats tab, in the order Left/Right step through them.
+pub const STATS_PAGES: [&str; 5] = ["Episodes per year", "Sagas", "Movies", "Characters", "Watch progress"];
+
+/// Episode count and runtime of one saga.
+#[derive(Debug, Clone)]
+pub struct SagaStats {
+    pub series: String,
+    pub saga: String,
+    pub episodes: usize,
+    pub minutes: u32,
+}
+
+/// Number of episodes of a series that aired in each year.
+pub fn episodes_per_year(series: &Series) -> Vec<(i32, u64)> {
+    let mut years: BTreeMap<i32, u64> = BTreeMap::new();
+    for episode in &series.episodes {
+        if let Some(date) = parse_release_date(&episode.release_date) {
+            *years.entry(date.year()).or_default() += 1;
+        }
+    }
+    years.into_iter().collect()
+}
+
+/// Every saga of every series, in guide and airing order.
+pub fn saga_stats(guide: &[Series]) -> Vec<SagaStats> {
+    let mut stats = Vec::new();
+    for series in guide {
+        for saga in series.sagas() {
+            let episodes: Vec<_> = series.episodes.iter().filter(|ep| ep.saga == saga).collect();
+            stats.push(SagaStats {
+                series: series.series.clone(),
+                saga: saga.to_string(),
+                episodes: episodes.len(),
+                minutes: episodes.iter().filter_map(|ep| parse_duration_minutes(&ep.duration)).sum(),
+            });
+        }
+    }
+    stats
+}
+
+/// Counts how often each value occurs, most frequent first, then by name.
+pub fn count_by<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(String, u64)> {
+    let mut counts: BTreeMap<&str, u64> = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut counts: Vec<(String, u64)> = counts.into_iter().map(|(value, count)| (value.to_string(), count)).collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+pub fn movies_per_director(movies: &[Movie]) -> Vec<(String, u64)> {
+    count_by(movies.iter().map(|movie| movie.director.as_str()))
+}
+
+pub fn movies_per_genre(movies: &[Movie]) -> Vec<(String, u64)> {
+    count_by(movies.iter().flat_map(|movie| movie.genres.iter().map(String::as_str)))
+}
+
+pub fn characters_per_race(characters: &[Character]) -> Vec<(String, u64)> {
+    count_by(characters.iter().map(|character| character.race.as_str()))
+}
+
+/// Running total of watched episodes and movies on each day something was
+/// marked as watched.
+pub fn watch_progress(user_data: &UserData) -> Vec<(NaiveDate, u64)> {
+    let mut per_day: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for date in user_data.watched.values() {
+        if let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            *per_day.entry(date).or_default() += 1;
+        }
+    }
+    let mut total = 0;
+    per_day.into_iter()
+        .map(|(date, count)| {
+            total += count;
+            (date, total)
+        })
+        .collect()
+}
@@ -0,0 +1,301 @@
+use serde::{Deserialize, Deserializer};
+use std::fs;
+use std::path::Path;
+use tui::style::{Color, Modifier, Style};
+
+/// The colors used by every draw function. User themes only need to set the
+/// colors they want to change; the rest are taken from the dark theme.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    #[serde(default)]
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub accent: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub heading: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub label: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub key: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub tag: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub sort_info: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub highlight_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub highlight_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub tab_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub tab_bg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub tab_highlight_fg: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub tab_highlight_bg: Color,
+    /// Plain mode: no colors, ASCII symbols and a text marker on the
+    /// selected item. Not settable from theme files.
+    #[serde(skip)]
+    pub plain: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            text: Color::White,
+            background: Color::Reset,
+            border: Color::Cyan,
+            accent: Color::LightCyan,
+            heading: Color::Yellow,
+            label: Color::Yellow,
+            key: Color::Green,
+            tag: Color::Green,
+            sort_info: Color::LightYellow,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::Yellow,
+            tab_fg: Color::Gray,
+            tab_bg: Color::Black,
+            tab_highlight_fg: Color::White,
+            tab_highlight_bg: Color::Blue,
+            plain: false,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            text: Color::Black,
+            background: Color::Reset,
+            border: Color::Blue,
+            accent: Color::Blue,
+            heading: Color::Magenta,
+            label: Color::Magenta,
+            key: Color::Blue,
+            tag: Color::Red,
+            sort_info: Color::Magenta,
+            highlight_fg: Color::White,
+            highlight_bg: Color::Blue,
+            tab_fg: Color::DarkGray,
+            tab_bg: Color::Reset,
+            tab_highlight_fg: Color::White,
+            tab_highlight_bg: Color::Blue,
+            plain: false,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            text: Color::White,
+            background: Color::Black,
+            border: Color::White,
+            accent: Color::LightYellow,
+            heading: Color::LightYellow,
+            label: Color::LightCyan,
+            key: Color::LightGreen,
+            tag: Color::LightGreen,
+            sort_info: Color::LightYellow,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::White,
+            tab_fg: Color::White,
+            tab_bg: Color::Black,
+            tab_highlight_fg: Color::Black,
+            tab_highlight_bg: Color::LightYellow,
+            plain: false,
+        }
+    }
+
+    /// Goku's gi: orange with blue accents.
+    pub fn saiyan() -> Self {
+        Self {
+            name: "saiyan".to_string(),
+            text: Color::Rgb(255, 236, 210),
+            background: Color::Reset,
+            border: Color::Rgb(255, 140, 0),
+            accent: Color::Rgb(255, 165, 0),
+            heading: Color::Rgb(255, 140, 0),
+            label: Color::Rgb(255, 200, 60),
+            key: Color::Rgb(80, 140, 255),
+            tag: Color::Rgb(80, 140, 255),
+            sort_info: Color::Rgb(255, 200, 60),
+            highlight_fg: Color::Rgb(20, 30, 90),
+            highlight_bg: Color::Rgb(255, 140, 0),
+            tab_fg: Color::Rgb(255, 200, 140),
+            tab_bg: Color::Rgb(20, 30, 90),
+            tab_highlight_fg: Color::Rgb(20, 30, 90),
+            tab_highlight_bg: Color::Rgb(255, 140, 0),
+            plain: false,
+        }
+    }
+
+    /// Used for NO_COLOR, serial consoles and screen readers.
+    pub fn plain() -> Self {
+        Self {
+            name: "plain".to_string(),
+            plain: true,
+            ..Self::dark()
+        }
+    }
+
+    pub fn builtin() -> Vec<Theme> {
+        vec![Self::dark(), Self::light(), Self::high_contrast(), Self::saiyan()]
+    }
+
+    /// Picks the ASCII fallback of a symbol in plain mode.
+    pub fn symbol<'a>(&self, unicode: &'a str, ascii: &'a str) -> &'a str {
+        if self.plain { ascii } else { unicode }
+    }
+
+    /// Marker drawn in front of the selected list item. Colored themes rely
+    /// on the highlight background instead.
+    pub fn highlight_symbol(&self) -> &'static str {
+        if self.plain { "> " } else { "" }
+    }
+
+    fn fg(&self, color: Color) -> Style {
+        if self.plain {
+            return Style::default();
+        }
+        Style::default().fg(color)
+    }
+
+    fn colors(&self, fg: Color, bg: Color) -> Style {
+        if self.plain {
+            return Style::default();
+        }
+        Style::default().fg(fg).bg(bg)
+    }
+
+    pub fn text(&self) -> Style {
+        self.fg(self.text)
+    }
+
+    pub fn border(&self) -> Style {
+        self.fg(self.border)
+    }
+
+    pub fn accent(&self) -> Style {
+        self.fg(self.accent)
+    }
+
+    pub fn heading(&self) -> Style {
+        self.fg(self.heading).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn label(&self) -> Style {
+        self.fg(self.label)
+    }
+
+    pub fn key(&self) -> Style {
+        self.fg(self.key)
+    }
+
+    pub fn tag(&self) -> Style {
+        self.fg(self.tag)
+    }
+
+    pub fn sort_info(&self) -> Style {
+        self.fg(self.sort_info)
+    }
+
+    pub fn highlight(&self) -> Style {
+        self.colors(self.highlight_fg, self.highlight_bg)
+    }
+
+    pub fn tabs(&self) -> Style {
+        self.colors(self.tab_fg, self.tab_bg)
+    }
+
+    pub fn tab_highlight(&self) -> Style {
+        self.colors(self.tab_highlight_fg, self.tab_highlight_bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn base(&self) -> Style {
+        self.colors(self.text, self.background)
+    }
+}
+
+/// Loads every `*.toml` theme from `dir`, sorted by name. A theme without a
+/// `name` is named after its file.
+pub fn load_user_themes(dir: &Path) -> Result<Vec<Theme>, Box<dyn std::error::Error>> {
+    let mut themes = Vec::new();
+    if !dir.is_dir() {
+        return Ok(themes);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+            continue;
+        }
+        let file_content = fs::read_to_string(&path)?;
+        let mut theme: Theme = toml::from_str(&file_content)
+            .map_err(|e| format!("Invalid theme file {}: {}", path.display(), e))?;
+        if theme.name.is_empty() {
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                theme.name = stem.to_string();
+            }
+        }
+        themes.push(theme);
+    }
+
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(themes)
+}
+
+/// Parses a color name ("yellow", "light_cyan"), a hex value ("#ff8800") or
+/// a 256-color index ("208").
+pub fn parse_color(s: &str) -> Result<Color, String> {
+    let normalized = s.trim().to_lowercase().replace(['_', '-', ' '], "");
+    let color = match normalized.as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        hex if hex.starts_with('#') => {
+            // Check the digits before slicing, as other characters may not
+            // be one byte long
+            let digits = &hex[1..];
+            if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("Invalid color: {}", s));
+            }
+            let channel = |range| u8::from_str_radix(&digits[range], 16).map_err(|_| format!("Invalid color: {}", s));
+            Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?)
+        }
+        index => Color::Indexed(index.parse().map_err(|_| format!("Invalid color: {}", s))?),
+    };
+    Ok(color)
+}
+
+fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_color(&s).map_err(serde::de::Error::custom)
+}
@@ -0,0 +1,190 @@
+use chrono::{Datelike, NaiveDate};
+use crate::app::{App, SearchResultType};
+use crate::data::parse_release_date;
+
+/// How much time one column of the timeline covers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimelineZoom {
+    /// A column per month, to see whole years at once.
+    Years,
+    /// A column per day, to see single months.
+    Months,
+}
+
+/// Selection and zoom of the Timeline tab.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    pub zoom: TimelineZoom,
+    /// Index of the selected lane in `lanes()`.
+    pub lane: usize,
+    /// Index of the selected item in its lane.
+    pub item: usize,
+}
+
+pub struct TimelineItem {
+    pub date: NaiveDate,
+    /// Whether `date` is the release in the configured region rather than
+    /// the original one it falls back to.
+    pub regional: bool,
+    pub target: SearchResultType,
+    pub label: String,
+}
+
+/// A saga and the dates of its first and last episode.
+pub struct SagaSpan {
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// One row of the timeline: a series, or the movies.
+pub struct Lane {
+    pub name: String,
+    /// Items in release order.
+    pub items: Vec<TimelineItem>,
+    pub sagas: Vec<SagaSpan>,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self { zoom: TimelineZoom::Years, lane: 0, item: 0 }
+    }
+}
+
+impl TimelineZoom {
+    pub fn toggle(self) -> Self {
+        match self {
+            TimelineZoom::Years => TimelineZoom::Months,
+            TimelineZoom::Months => TimelineZoom::Years,
+        }
+    }
+
+    /// Column of `date` counted from `origin`, which starts a month.
+    pub fn column(self, origin: NaiveDate, date: NaiveDate) -> i64 {
+        match self {
+            TimelineZoom::Years => {
+                (date.year() - origin.year()) as i64 * 12 + date.month0() as i64 - origin.month0() as i64
+            }
+            TimelineZoom::Months => (date - origin).num_days(),
+        }
+    }
+}
+
+/// The series lanes, in guide order, followed by the movies lane. Items
+/// not released in the configured region are placed by their original
+/// release date; items without a readable one are left out.
+pub fn lanes(app: &App) -> Vec<Lane> {
+    let mut lanes: Vec<Lane> = app.guide.iter().enumerate()
+        .map(|(series_index, series)| {
+            let mut items: Vec<TimelineItem> = series.episodes.iter().enumerate()
+                .filter_map(|(episode_index, episode)| {
+                    Some(TimelineItem {
+                        date: parse_release_date(episode.release_date_in(&app.region))?,
+                        regional: episode.regional_release_date(&app.region).is_some(),
+                        target: SearchResultType::Episode(series_index, episode_index),
+                        label: format!("{} {}: {}", series.series, episode.episode_number, episode.display_title(app.title_language)),
+                    })
+                })
+                .collect();
+            items.sort_by_key(|item| item.date);
+
+            let sagas = series.sagas().into_iter()
+                .filter_map(|saga| {
+                    let dates = series.episodes.iter()
+                        .filter(|ep| ep.saga == saga)
+                        .filter_map(|ep| parse_release_date(ep.release_date_in(&app.region)));
+                    let (start, end) = dates.fold(None, |span: Option<(NaiveDate, NaiveDate)>, date| match span {
+                        Some((start, end)) => Some((start.min(date), end.max(date))),
+                        None => Some((date, date)),
+                    })?;
+                    Some(SagaSpan { name: saga.to_string(), start, end })
+                })
+                .collect();
+
+            Lane { name: series.series.clone(), items, sagas }
+        })
+        .collect();
+
+    let mut movies: Vec<TimelineItem> = app.movies.iter().enumerate()
+        .filter_map(|(movie_index, movie)| {
+            Some(TimelineItem {
+                date: parse_release_date(movie.release_date_in(&app.region))?,
+                regional: movie.regional_release_date(&app.region).is_some(),
+                target: SearchResultType::Movie(movie_index),
+                label: app.locale.fill("Movie {}: {}", &[&movie.number, &movie.display_title(app.title_language)]),
+            })
+        })
+        .collect();
+    movies.sort_by_key(|item| item.date);
+    lanes.push(Lane { name: app.locale.tr("Movies").to_string(), items: movies, sagas: Vec::new() });
+    lanes
+}
+
+/// First day of the month of the earliest item, where column 0 starts.
+pub fn origin(lanes: &[Lane]) -> Option<NaiveDate> {
+    let first = lanes.iter().filter_map(|lane| lane.items.first()).map(|item| item.date).min()?;
+    first.with_day(1)
+}
+
+impl Timeline {
+    /// Moves the selection `delta` items along the current lane.
+    pub fn step(&mut self, lanes: &[Lane], delta: isize) {
+        let len = lanes.get(self.lane).map_or(0, |lane| lane.items.len());
+        if len > 0 {
+            self.item = (self.item as isize + delta).clamp(0, len as isize - 1) as usize;
+        }
+    }
+
+    pub fn select_first(&mut self) {
+        self.item = 0;
+    }
+
+    pub fn select_last(&mut self, lanes: &[Lane]) {
+        self.item = lanes.get(self.lane).map_or(0, |lane| lane.items.len().saturating_sub(1));
+    }
+
+    /// Switches `delta` lanes up or down, selecting the item of the new
+    /// lane closest in time to the current one.
+    pub fn change_lane(&mut self, lanes: &[Lane], delta: isize) {
+        if lanes.is_empty() {
+            return;
+        }
+        let date = self.selected(lanes).map(|item| item.date);
+        self.lane = (self.lane as isize + delta).clamp(0, lanes.len() as isize - 1) as usize;
+        self.item = match date {
+            Some(date) => closest_item(&lanes[self.lane], date),
+            None => 0,
+        };
+    }
+
+    /// Jumps to the first item of the current lane released at least a year
+    /// (or, zoomed in, a month) after or before the selected one.
+    pub fn jump(&mut self, lanes: &[Lane], forward: bool) {
+        let (lane, date) = match (lanes.get(self.lane), self.selected(lanes)) {
+            (Some(lane), Some(item)) => (lane, item.date),
+            _ => return,
+        };
+        let months = match self.zoom {
+            TimelineZoom::Years => 12,
+            TimelineZoom::Months => 1,
+        };
+        let target = if forward {
+            date.checked_add_months(chrono::Months::new(months))
+        } else {
+            date.checked_sub_months(chrono::Months::new(months))
+        };
+        if let Some(target) = target {
+            self.item = closest_item(lane, target);
+        }
+    }
+
+    pub fn selected<'a>(&self, lanes: &'a [Lane]) -> Option<&'a TimelineItem> {
+        lanes.get(self.lane)?.items.get(self.item)
+    }
+}
+
+fn closest_item(lane: &Lane, date: NaiveDate) -> usize {
+    lane.items.iter().enumerate()
+        .min_by_key(|(_, item)| (item.date - date).num_days().abs())
+        .map_or(0, |(index, _)| index)
+}
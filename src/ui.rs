@@ -1,40 +1,638 @@
+use chrono::Datelike;
 use tui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    text::{Span, Spans},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    symbols,
+    text::{Span, Spans, Text},
+    widgets::{Axis, BarChart, Block, Borders, Cell, Chart, Clear, Dataset, Gauge, GraphType, List, ListItem, ListState, Paragraph, Row, Table, Tabs, Widget},
     Frame,
 };
-use crate::app::{App, AppMode, SearchResultType, EpisodeSortMethod, SortOrder, MovieSortMethod};
+use crate::app::{App, AppMode, PromptKind, SearchResultType, EpisodeSortMethod, SortOrder, MovieSortMethod, TAB_TITLES};
+use crate::data::{Movie, ORIGINAL_REGION, Release, TitleLanguage, parse_duration_minutes, region_name, related_movies};
+use crate::facets::FilterTarget;
+use crate::family::{Generation, relatives, tree_rows};
+use crate::keymap::Action;
+use crate::stats::{STATS_PAGES, SagaStats, characters_per_race, episodes_per_year, movies_per_director, movies_per_genre, saga_stats, watch_progress};
+use crate::theme::Theme;
+use crate::timeline::{TimelineZoom, lanes, origin};
+use crate::user_data::{MAX_RATING, episode_key, movie_key, character_key};
 
 pub fn draw_ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let theme = app.theme().clone();
     let size = f.size();
+    f.render_widget(Block::default().style(theme.base()), size);
     let layout_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(1),
+            Constraint::Length(1),
         ].as_ref())
         .split(size);
 
-    draw_main_tabs(f, app, layout_chunks[0]);
+    draw_main_tabs(f, app, &theme, layout_chunks[0]);
+    draw_status_bar(f, app, &theme, layout_chunks[2]);
 
     match app.app_mode {
-        AppMode::Help => draw_help_screen(f, layout_chunks[1]),
-        AppMode::Search => draw_search_tab(f, app, layout_chunks[1]),
+        AppMode::Help => draw_help_screen(f, app, &theme, layout_chunks[1]),
+        AppMode::Search => draw_search_tab(f, app, &theme, layout_chunks[1]),
+        AppMode::Quiz => draw_quiz(f, app, &theme, layout_chunks[1]),
         _ => {
             match app.selected_tab {
-                0 => draw_episodes_tab(f, app, layout_chunks[1]),
-                1 => draw_movies_tab(f, app, layout_chunks[1]),
-                2 => draw_characters_tab(f, app, layout_chunks[1]),
+                0 => draw_episodes_tab(f, app, &theme, layout_chunks[1]),
+                1 => draw_movies_tab(f, app, &theme, layout_chunks[1]),
+                2 => draw_characters_tab(f, app, &theme, layout_chunks[1]),
+                3 => draw_favorites_tab(f, app, &theme, layout_chunks[1]),
+                4 => draw_stats_tab(f, app, &theme, layout_chunks[1]),
+                5 => draw_timeline_tab(f, app, &theme, layout_chunks[1]),
                 _ => {}
             }
         }
     }
+
+    if app.palette.is_some() {
+        draw_command_palette(f, app, &theme, size);
+    }
+    if app.on_this_day.is_some() {
+        draw_on_this_day(f, app, &theme, size);
+    }
+    if app.filter_panel.is_some() {
+        draw_filter_panel(f, app, &theme, size);
+    }
+
+    if theme.plain {
+        f.render_widget(AsciiBorders, size);
+    }
+}
+
+/// Rewrites the box drawing and chart characters already rendered in an
+/// area with their ASCII equivalents. Drawn last, over the whole frame, in
+/// plain mode.
+struct AsciiBorders;
+
+impl Widget for AsciiBorders {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                let ascii = match cell.symbol.as_str() {
+                    "─" | "━" | "═" => "-",
+                    "│" | "┃" | "║" => "|",
+                    "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "├" | "┤" | "┬" | "┴" | "┼"
+                    | "╔" | "╗" | "╚" | "╝" | "┏" | "┓" | "┗" | "┛" => "+",
+                    "•" => "*",
+                    _ => continue,
+                };
+                cell.set_symbol(ascii);
+            }
+        }
+    }
+}
+
+fn draw_command_palette<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
+    let locale = app.locale;
+    let palette = match app.palette.as_mut() {
+        Some(palette) => palette,
+        None => return,
+    };
+    let popup = centered_rect(60, 60, area);
+    f.render_widget(Clear, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ].as_ref())
+        .split(popup);
+
+    let input = Paragraph::new(format!("> {}", palette.query))
+        .style(theme.accent())
+        .block(Block::default().borders(Borders::ALL).title(locale.tr("Command Palette")).border_style(theme.border()));
+    f.render_widget(input, chunks[0]);
+
+    let items: Vec<ListItem> = palette.matches.iter()
+        .map(|&index| ListItem::new(palette.entries[index].label.clone()))
+        .collect();
+    let list = List::new(items)
+        .style(theme.base())
+        .block(Block::default().borders(Borders::ALL).border_style(theme.border()))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol());
+    f.render_stateful_widget(list, chunks[1], &mut palette.list_state);
 }
 
-fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layout::Rect) {
+fn draw_on_this_day<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
+    let locale = app.locale;
+    let footer = match app.keymap.keys_for(Action::Select).first() {
+        Some(key) => locale.fill("{} open  any other key closes", &[key]),
+        None => locale.tr("Any key closes").to_string(),
+    };
+    let panel = match app.on_this_day.as_mut() {
+        Some(panel) => panel,
+        None => return,
+    };
+    let popup = centered_rect(70, 60, area);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(locale.fill("On This Day: {}", &[&panel.today.format("%B %-d")]))
+        .border_style(theme.border())
+        .style(theme.base());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+        .split(inner);
+
+    let today = panel.today;
+    let items: Vec<ListItem> = panel.anniversaries.iter()
+        .map(|anniversary| {
+            let day = if anniversary.date == today {
+                locale.tr("Today").to_string()
+            } else {
+                anniversary.date.format("%a %b %-d").to_string()
+            };
+            let years = match anniversary.years_ago() {
+                1 => locale.tr("1 year ago").to_string(),
+                years => locale.fill("{} years ago", &[&years]),
+            };
+            ListItem::new(Spans::from(vec![
+                Span::styled(format!("{:<12}", day), theme.label()),
+                Span::styled(format!("{:<14}", years), theme.accent()),
+                Span::raw(anniversary.label.clone()),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol());
+    f.render_stateful_widget(list, chunks[0], &mut panel.list_state);
+
+    let footer = Paragraph::new(footer).style(theme.label());
+    f.render_widget(footer, chunks[1]);
+}
+
+/// The options of the filter panel under a heading per kind, each with
+/// the number of items picking it would leave.
+fn draw_filter_panel<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
+    let panel = match app.filter_panel.as_ref() {
+        Some(panel) => panel,
+        None => return,
+    };
+    let select_key = app.keymap.keys_for(Action::Select).first().map(|key| key.to_string());
+    let close_key = app.keymap.keys_for(Action::Back).first().map_or(app.locale.tr("any other key").to_string(), |key| key.to_string());
+    let (title, footer) = if panel.jump {
+        (
+            app.locale.tr("Show Movies With").to_string(),
+            app.locale.fill("{} show  {} close", &[&select_key.unwrap_or_else(|| "Space".to_string()), &close_key]),
+        )
+    } else {
+        let (noun, total) = match panel.target {
+            FilterTarget::Characters => ("Characters", app.characters.len()),
+            FilterTarget::Movies => ("Movies", app.movies.len()),
+        };
+        (
+            app.locale.fill("Filter {}: {} of {}", &[&app.locale.tr(noun), &app.current_list_len(), &total]),
+            app.locale.fill("{} pick  Backspace clear  {} close", &[&select_key.map_or("Space".to_string(), |key| format!("Space/{}", key)), &close_key]),
+        )
+    };
+    let filter = app.filter(panel.target);
+    let popup = centered_rect(50, 70, area);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(theme.border())
+        .style(theme.base());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+        .split(inner);
+
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_row = 0;
+    for &kind in &panel.kinds {
+        if !items.is_empty() {
+            items.push(ListItem::new(Spans::from("")));
+        }
+        items.push(ListItem::new(Span::styled(app.locale.tr(kind.title()), theme.heading().add_modifier(Modifier::BOLD))));
+        for (index, option) in panel.options.iter().enumerate().filter(|(_, option)| option.kind == kind) {
+            if index == panel.selected {
+                selected_row = items.len();
+            }
+            let picked = filter.is_picked(kind, &option.value) && !panel.jump;
+            let mark = match (panel.jump, picked) {
+                (true, _) => "",
+                (false, true) => theme.symbol("[✓] ", "[x] "),
+                (false, false) => "[ ] ",
+            };
+            let style = if picked {
+                theme.accent().add_modifier(Modifier::BOLD)
+            } else if option.count == 0 {
+                theme.label()
+            } else {
+                theme.text()
+            };
+            items.push(ListItem::new(Spans::from(vec![
+                Span::styled(format!("{}{}", mark, option.value), style),
+                Span::styled(format!(" ({})", option.count), theme.label()),
+            ])));
+        }
+    }
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected_row));
+    let list = List::new(items)
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol());
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+
+    let footer = Paragraph::new(footer).style(theme.label());
+    f.render_widget(footer, chunks[1]);
+}
+
+/// A rectangle of `percent_x` by `percent_y` of `area`, centered in it.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ].as_ref())
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ].as_ref())
+        .split(vertical[1])[1]
+}
+
+fn draw_status_bar<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    if let Some(prompt) = &app.prompt {
+        let label = app.locale.tr(match prompt.kind {
+            PromptKind::Find => "/",
+            PromptKind::GoTo => "Go to (42, dbz 150, #300): ",
+            PromptKind::Note(_) => "Note: ",
+            PromptKind::Rating(_) => "Rating (1-10, empty to clear): ",
+            PromptKind::RandomFilter => "Random from (dbz saga:frieza 1989-1995 max:30m unwatched canon): ",
+        });
+        // Long input, such as a note, scrolls so that its end stays in view
+        let text = format!("{}{}", label, prompt.input);
+        let overflow = text.chars().count().saturating_sub((area.width as usize).saturating_sub(1));
+        let prompt = Paragraph::new(text.chars().skip(overflow).collect::<String>())
+            .style(theme.accent());
+        f.render_widget(prompt, area);
+        return;
+    }
+
+    let episode_count = |series_index: usize| app.sorted_episodes(series_index).len();
+    let (mode, position) = match app.app_mode {
+        AppMode::EpisodesSeries(series_index) => {
+            ("EPISODES", list_position(app, "episode {}/{}", app.list_state.selected(), episode_count(series_index)))
+        }
+        AppMode::Details(series_index, episode_index) => {
+            ("EPISODE", row_position(app, "episode {}/{}", app.sorted_episodes(series_index), episode_index))
+        }
+        AppMode::MoviesList => ("MOVIES", list_position(app, "movie {}/{}", app.list_state.selected(), app.current_list_len())),
+        AppMode::MovieDetails(movie_index) => ("MOVIE", row_position(app, "movie {}/{}", &app.visible_movies(), movie_index)),
+        AppMode::Search => ("SEARCH", list_position(app, "result {}/{}", app.list_state.selected(), app.search_results.len())),
+        AppMode::SagaDetails(_, _) => ("SAGA", None),
+        AppMode::Characters => ("CHARACTERS", list_position(app, "character {}/{}", app.list_state.selected(), app.current_list_len())),
+        AppMode::CharacterDetails(character_index) => {
+            ("CHARACTER", row_position(app, "character {}/{}", &app.visible_characters(), character_index))
+        }
+        AppMode::FamilyTree(_) => ("FAMILY", None),
+        AppMode::CharacterComparison(_, _) => ("COMPARE", None),
+        AppMode::Favorites => ("FAVORITES", list_position(app, "favorite {}/{}", app.list_state.selected(), app.current_list_len())),
+        AppMode::Stats => ("STATS", list_position(app, "page {}/{}", Some(app.stats_page), STATS_PAGES.len())),
+        AppMode::Timeline => ("TIMELINE", None),
+        AppMode::Quiz => ("QUIZ", app.quiz.as_ref().map(|quiz| app.locale.fill("score {}/{}", &[&quiz.correct, &quiz.answered]))),
+        AppMode::Help => ("HELP", None),
+    };
+
+    let mut left = vec![Span::styled(format!(" {} ", app.locale.tr(mode)), theme.tab_highlight())];
+    let mut push = |text: String, style| {
+        left.push(Span::raw("  "));
+        left.push(Span::styled(text, style));
+    };
+    if let Some(position) = position {
+        push(position, theme.text());
+    }
+    match app.app_mode {
+        AppMode::EpisodesSeries(series_index)
+        | AppMode::Details(series_index, _)
+        | AppMode::SagaDetails(series_index, _) => {
+            push(app.locale.fill("sort: {}", &[&episode_sort_label(app, theme)]), theme.sort_info());
+            push(app.locale.fill("watched: {}%", &[&app.series_watched_percent(series_index)]), theme.text());
+        }
+        AppMode::MoviesList | AppMode::MovieDetails(_) => {
+            push(app.locale.fill("sort: {}", &[&movie_sort_label(app, theme)]), theme.sort_info());
+        }
+        _ => {}
+    }
+    if let Some(count) = app.pending_count {
+        push(count.to_string(), theme.accent());
+    }
+    if let Some(message) = app.current_status() {
+        push(message.to_string(), theme.accent());
+    }
+
+    // Drop the least important hints rather than cutting off the status
+    let left_width: usize = left.iter().map(|span| span.width()).sum();
+    let available = (area.width as usize).saturating_sub(left_width + 2);
+    let mut hints = status_hints(app);
+    while !hints.is_empty() && hints.join("  ").chars().count() > available {
+        hints.pop();
+    }
+    let hints = hints.join("  ");
+    let hints_width = hints.chars().count() as u16 + 1;
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(hints_width),
+        ].as_ref())
+        .split(area);
+
+    f.render_widget(Paragraph::new(Spans::from(left)), chunks[0]);
+    f.render_widget(
+        Paragraph::new(Span::styled(hints, theme.key())).alignment(tui::layout::Alignment::Right),
+        chunks[1],
+    );
+}
+
+fn watched_mark<'a>(app: &App, theme: &'a Theme, key: &str) -> &'a str {
+    if app.user_data.is_watched(key) {
+        theme.symbol("✓", "*")
+    } else {
+        " "
+    }
+}
+
+fn favorite_mark<'a>(app: &App, theme: &'a Theme, key: &str) -> &'a str {
+    if app.user_data.is_favorite(key) {
+        theme.symbol("★", "+")
+    } else {
+        " "
+    }
+}
+
+/// Rating shown after the title of a rated episode or movie in a list.
+fn rating_suffix(app: &App, key: &str) -> String {
+    app.user_data.rating(key)
+        .map(|rating| format!("  {}/{}", rating, MAX_RATING))
+        .unwrap_or_default()
+}
+
+/// The user's own favorite mark, rating and note of an item, for the end of
+/// a details view.
+fn personal_lines<'a>(app: &'a App, theme: &Theme, key: &str) -> Vec<Spans<'a>> {
+    let mut lines = Vec::new();
+    if app.user_data.is_favorite(key) {
+        lines.push(Spans::from(vec![
+            Span::styled(app.locale.tr("Favorite: "), theme.label()),
+            Span::raw(theme.symbol("★", app.locale.tr("yes"))),
+        ]));
+    }
+    if let Some(rating) = app.user_data.rating(key) {
+        lines.push(Spans::from(vec![
+            Span::styled(app.locale.tr("Your Rating: "), theme.label()),
+            Span::raw(format!("{}/{}", rating, MAX_RATING)),
+        ]));
+    }
+    if let Some(note) = app.user_data.note(key) {
+        lines.push(Spans::from(vec![
+            Span::styled(app.locale.tr("Your Note: "), theme.label()),
+            Span::raw(note),
+        ]));
+    }
+    if !lines.is_empty() {
+        lines.insert(0, Spans::from(""));
+    }
+    lines
+}
+
+/// The release date in the configured region, falling back to the original
+/// one, followed by the other releases when there are any.
+fn release_lines<'a>(app: &App, theme: &Theme, original: &'a str, releases: &'a [Release]) -> Vec<Spans<'a>> {
+    let describe = |region: &str, date: &str, dub: Option<&String>| match dub {
+        Some(dub) => app.locale.fill("{} ({}, {} dub)", &[&date, &region_name(region, app.locale), dub]),
+        None => format!("{} ({})", date, region_name(region, app.locale)),
+    };
+    let regional = releases.iter().find(|release| release.region == app.region);
+    let shown = match regional {
+        Some(release) => describe(&release.region, &release.date, release.dub.as_ref()),
+        None if releases.is_empty() && app.region == ORIGINAL_REGION => original.to_string(),
+        None => describe(ORIGINAL_REGION, original, None),
+    };
+    let mut lines = vec![Spans::from(vec![Span::styled(app.locale.tr("Release Date: "), theme.label()), Span::raw(shown)])];
+    let mut others: Vec<String> = Vec::new();
+    if regional.is_some() {
+        others.push(describe(ORIGINAL_REGION, original, None));
+    }
+    others.extend(releases.iter()
+        .filter(|release| release.region != app.region)
+        .map(|release| describe(&release.region, &release.date, release.dub.as_ref())));
+    if !others.is_empty() {
+        lines.push(Spans::from(vec![Span::styled(app.locale.tr("Other Releases: "), theme.label()), Span::raw(others.join("; "))]));
+    }
+    lines
+}
+
+/// The titles of an episode or movie other than the one in the heading.
+fn title_lines<'a>(
+    app: &App,
+    theme: &Theme,
+    english: &'a str,
+    japanese: &'a Option<String>,
+    romaji: &'a Option<String>,
+    alternates: &'a [String],
+) -> Vec<Spans<'a>> {
+    let mut lines = Vec::new();
+    if app.title_language != TitleLanguage::English {
+        lines.push(Spans::from(vec![Span::styled(app.locale.tr("English Title: "), theme.label()), Span::raw(english)]));
+    }
+    let original = match (japanese, romaji) {
+        (Some(japanese), Some(romaji)) => Some(format!("{} ({})", japanese, romaji)),
+        (Some(title), None) | (None, Some(title)) => Some(title.clone()),
+        (None, None) => None,
+    };
+    if let Some(original) = original {
+        lines.push(Spans::from(vec![Span::styled(app.locale.tr("Japanese Title: "), theme.label()), Span::raw(original)]));
+    }
+    if !alternates.is_empty() {
+        lines.push(Spans::from(vec![Span::styled(app.locale.tr("Also Known As: "), theme.label()), Span::raw(alternates.join(", "))]));
+    }
+    lines
+}
+
+/// Where a movie fits in the TV continuity, e.g. "Dragon Ball Super, around
+/// episodes 19-27 (Golden Frieza Saga), canon".
+fn movie_placement(app: &App, movie: &Movie) -> String {
+    let continuity = app.locale.tr(if movie.canon { "canon" } else { "non-canon" });
+    let era = match &movie.era {
+        Some(era) => era,
+        None => return continuity.to_string(),
+    };
+    let episodes = match movie.episodes {
+        Some((first, last)) if first == last => app.locale.fill(", around episode {}", &[&first]),
+        Some((first, last)) => app.locale.fill(", around episodes {}-{}", &[&first, &last]),
+        None => app.locale.tr(", no fixed placement").to_string(),
+    };
+    let sagas = app.guide.iter()
+        .find(|series| series.series == *era)
+        .map(|series| movie.sagas(series).join(", "))
+        .filter(|sagas| !sagas.is_empty())
+        .map(|sagas| format!(" ({})", sagas))
+        .unwrap_or_default();
+    format!("{}{}{}, {}", era, episodes, sagas, continuity)
+}
+
+/// The movies taking place around an episode or saga, for its details.
+fn related_movie_lines<'a>(app: &'a App, theme: &Theme, movie_indices: &[usize]) -> Vec<Spans<'a>> {
+    if movie_indices.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![
+        Spans::from(""),
+        Spans::from(Span::styled(app.locale.tr("Related Movies:"), theme.label())),
+    ];
+    for &movie_index in movie_indices {
+        let movie = &app.movies[movie_index];
+        lines.push(Spans::from(vec![
+            Span::raw(format!("- {}: {}", movie.number, movie.display_title(app.title_language))),
+            Span::styled(if movie.canon { "" } else { app.locale.tr(" (non-canon)") }, theme.label()),
+        ]));
+    }
+    lines
+}
+
+/// The position of the selection, e.g. "episode 3/153", from a template
+/// such as "episode {}/{}".
+fn list_position(app: &App, template: &'static str, selected: Option<usize>, len: usize) -> Option<String> {
+    selected.filter(|_| len > 0).map(|index| app.locale.fill(template, &[&(index + 1), &len]))
+}
+
+/// Like `list_position` for the details of the item at `index`, counted by
+/// its row in the list it opens from; `None` when the list leaves it out.
+fn row_position(app: &App, template: &'static str, rows: &[usize], index: usize) -> Option<String> {
+    list_position(app, template, rows.iter().position(|&row| row == index), rows.len())
+}
+
+/// "key label" pairs for the actions that matter in the current mode.
+fn status_hints(app: &App) -> Vec<String> {
+    let actions: &[Action] = match app.app_mode {
+        AppMode::EpisodesSeries(_) => &[
+            Action::Select, Action::Search, Action::GoTo, Action::SortMethod, Action::ToggleWatched, Action::ToggleFavorite,
+            Action::CommandPalette, Action::Help, Action::Quit,
+        ],
+        AppMode::MoviesList => &[
+            Action::Select, Action::Search, Action::Filter, Action::GoTo, Action::SortMethod, Action::ToggleWatched,
+            Action::ToggleFavorite, Action::CommandPalette, Action::Help, Action::Quit,
+        ],
+        AppMode::Details(_, _) => &[
+            Action::Back, Action::ToggleWatched, Action::Rate, Action::EditNote, Action::ToggleFavorite, Action::Help,
+            Action::Quit,
+        ],
+        AppMode::MovieDetails(_) => &[
+            Action::Back, Action::ToggleWatched, Action::Rate, Action::EditNote, Action::ToggleFavorite, Action::Filter,
+            Action::Help, Action::Quit,
+        ],
+        AppMode::SagaDetails(_, _) => &[Action::Back, Action::CommandPalette, Action::Help, Action::Quit],
+        AppMode::Characters => &[
+            Action::Select, Action::Search, Action::Filter, Action::ToggleFavorite, Action::Compare, Action::NextTab,
+            Action::CommandPalette, Action::Help, Action::Quit,
+        ],
+        AppMode::CharacterDetails(_) => &[
+            Action::Back, Action::FamilyTree, Action::ToggleFavorite, Action::EditNote, Action::Help, Action::Quit,
+        ],
+        AppMode::CharacterComparison(_, _) => &[Action::Back, Action::Help, Action::Quit],
+        AppMode::FamilyTree(_) => &[Action::Select, Action::NextSeries, Action::Down, Action::Back, Action::Help, Action::Quit],
+        AppMode::Favorites => &[
+            Action::Select, Action::ToggleFavorite, Action::EditNote, Action::NextTab, Action::Help, Action::Quit,
+        ],
+        AppMode::Stats => &[Action::NextSeries, Action::NextTab, Action::CommandPalette, Action::Help, Action::Quit],
+        AppMode::Timeline => &[
+            Action::Select, Action::NextSeries, Action::Down, Action::Zoom, Action::NextTab, Action::Help, Action::Quit,
+        ],
+        AppMode::Search => &[Action::Select, Action::Back],
+        AppMode::Quiz => &[Action::Select, Action::Down, Action::Back, Action::Help, Action::Quit],
+        AppMode::Help => &[Action::Back],
+    };
+    // Offer a re-roll while a random pick is open
+    let rerolling = app.random_pick.as_ref().is_some_and(|(_, mode)| *mode == app.app_mode);
+    let actions = if rerolling { [&[Action::Random], actions].concat() } else { actions.to_vec() };
+    actions.into_iter()
+        .filter_map(|action| {
+            // Left/Right step through the pages of the Stats tab and the
+            // releases of the Timeline tab, Up/Down through its lanes
+            let hint = match (&app.app_mode, action) {
+                (AppMode::Stats, Action::NextSeries) => "page",
+                (AppMode::Timeline, Action::NextSeries) => "release",
+                (AppMode::Timeline, Action::Down) => "lane",
+                (AppMode::FamilyTree(_), Action::Select) => "their tree",
+                (AppMode::FamilyTree(_), Action::NextSeries) => "relative",
+                (AppMode::FamilyTree(_), Action::Down) => "generation",
+                (AppMode::MovieDetails(_), Action::Filter) => "similar",
+                (AppMode::Quiz, Action::Select) => "answer",
+                (AppMode::Quiz, Action::Down) => "choice",
+                (AppMode::Quiz, Action::Back) => "stop",
+                _ => action.hint(),
+            };
+            app.keymap.keys_for(action).first().map(|key| format!("{} {}", key, app.locale.tr(hint)))
+        })
+        .collect()
+}
+
+fn episode_sort_label(app: &App, theme: &Theme) -> String {
+    let sort_method = match app.episode_sort_method {
+        EpisodeSortMethod::EpisodeNumber => "Ep#",
+        EpisodeSortMethod::Title => "Title",
+        EpisodeSortMethod::ReleaseDate => "Date",
+        EpisodeSortMethod::Rating => "Rating",
+    };
+    format!("{} {}", app.locale.tr(sort_method), sort_arrow(&app.episode_sort_order, theme))
+}
+
+fn movie_sort_label(app: &App, theme: &Theme) -> String {
+    let sort_method = match app.movie_sort_method {
+        MovieSortMethod::Number => "Num",
+        MovieSortMethod::Title => "Title",
+        MovieSortMethod::ReleaseDate => "Date",
+        MovieSortMethod::Rating => "Rating",
+    };
+    format!("{} {}", app.locale.tr(sort_method), sort_arrow(&app.movie_sort_order, theme))
+}
+
+fn sort_arrow<'a>(order: &SortOrder, theme: &'a Theme) -> &'a str {
+    match order {
+        SortOrder::Ascending => theme.symbol("↑", "^"),
+        SortOrder::Descending => theme.symbol("↓", "v"),
+    }
+}
+
+/// Tab title, prefixed with the selection marker in plain mode where the
+/// highlight has no color.
+fn tab_title(theme: &Theme, title: &str, selected: bool) -> String {
+    if selected {
+        format!("{}{}", theme.highlight_symbol(), title)
+    } else {
+        title.to_string()
+    }
+}
+
+fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
     let search_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -44,8 +642,8 @@ fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layou
         .split(area);
 
     let search_input = Paragraph::new(app.search_query.as_ref())
-        .style(Style::default().fg(Color::LightCyan))
-        .block(Block::default().borders(Borders::ALL).title("Search").style(Style::default().fg(Color::White)));
+        .style(theme.accent())
+        .block(Block::default().borders(Borders::ALL).title(app.locale.tr("Search")).style(theme.text()).border_style(theme.border()));
     f.render_widget(search_input, search_layout[0]);
 
     let results: Vec<ListItem> = app.search_results.iter()
@@ -53,10 +651,11 @@ fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layou
             let result_type = match result.result_type {
                 SearchResultType::Episode(_, _) => "Episode",
                 SearchResultType::Movie(_) => "Movie",
+                SearchResultType::Character(_) => "Character",
             };
             ListItem::new(vec![
                 Spans::from(vec![
-                    Span::styled(format!("[{}] ", result_type), Style::default().fg(Color::Green)),
+                    Span::styled(format!("[{}] ", app.locale.tr(result_type)), theme.tag()),
                     Span::raw(&result.title),
                 ]),
             ])
@@ -64,29 +663,30 @@ fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layou
         .collect();
 
     let results_list = List::new(results)
-        .block(Block::default().borders(Borders::ALL).title("Results"))
-        .highlight_style(Style::default().bg(Color::Yellow));
+        .block(Block::default().borders(Borders::ALL).title(app.locale.tr("Results")).border_style(theme.border()))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol());
 
+    app.list_height = search_layout[1].height.saturating_sub(2) as usize;
     f.render_stateful_widget(results_list, search_layout[1], &mut app.list_state);
 }
 
-fn draw_main_tabs<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
-    let tab_titles = ["Episodes", "Movies", "Characters"];
-    let spans: Vec<Spans> = tab_titles.iter().map(|&t| {
-        Spans::from(vec![Span::styled(t, Style::default().fg(Color::White))])
+fn draw_main_tabs<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    let spans: Vec<Spans> = TAB_TITLES.iter().enumerate().map(|(i, &t)| {
+        Spans::from(vec![Span::raw(tab_title(theme, app.locale.tr(t), i == app.selected_tab))])
     }).collect();
 
     let tabs = Tabs::new(spans)
-        .block(Block::default().borders(Borders::BOTTOM).title("Main Tabs").style(Style::default().fg(Color::White)))
-        .style(Style::default().bg(Color::Black).fg(Color::Gray))
-        .highlight_style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::BOTTOM).title(app.locale.tr("Main Tabs")).style(theme.text()))
+        .style(theme.tabs())
+        .highlight_style(theme.tab_highlight())
         .divider(Span::raw(" | "))
         .select(app.selected_tab);
 
     f.render_widget(tabs, area);
 }
 
-fn draw_episodes_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layout::Rect) {
+fn draw_episodes_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
     let layout_with_series_tabs = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -95,105 +695,137 @@ fn draw_episodes_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::lay
         ].as_ref())
         .split(area);
 
-    draw_series_tabs(f, app, layout_with_series_tabs[0]);
+    draw_series_tabs(f, app, theme, layout_with_series_tabs[0]);
 
     match app.app_mode {
         AppMode::EpisodesSeries(series_index) => {
-            draw_episodes_list(f, app, series_index, layout_with_series_tabs[1]);
+            match split_preview_area(app, layout_with_series_tabs[1]) {
+                Some((list_area, preview_area)) => {
+                    draw_episodes_list(f, app, theme, series_index, list_area);
+                    if let Some(episode_index) = app.selected_episode(series_index) {
+                        draw_episode_details(f, app, theme, series_index, episode_index, preview_area);
+                    }
+                }
+                None => draw_episodes_list(f, app, theme, series_index, layout_with_series_tabs[1]),
+            }
         }
         AppMode::Details(series_index, episode_index) => {
-            draw_episode_details(f, app, series_index, episode_index, layout_with_series_tabs[1]);
+            draw_episode_details(f, app, theme, series_index, episode_index, layout_with_series_tabs[1]);
+        }
+        AppMode::SagaDetails(series_index, ref saga) => {
+            draw_saga_details(f, app, theme, series_index, saga, layout_with_series_tabs[1]);
         }
         _ => {}
     }
 }
 
-fn draw_series_tabs<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
+/// Splits a list area into list and preview panes when the terminal is wide
+/// enough, otherwise the list keeps the whole area.
+fn split_preview_area(app: &App, area: Rect) -> Option<(Rect, Rect)> {
+    if area.width < app.split_min_width {
+        return None;
+    }
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(45),
+            Constraint::Percentage(55),
+        ].as_ref())
+        .split(area);
+    Some((panes[0], panes[1]))
+}
+
+fn draw_series_tabs<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
     let series_names: Vec<String> = app.guide.iter()
         .map(|series| series.series.clone())
         .collect();
 
-    let series_tabs: Vec<Spans> = series_names.iter().map(|name| {
-        Spans::from(vec![Span::styled(name, Style::default().fg(Color::White))])
+    let series_tabs: Vec<Spans> = series_names.iter().enumerate().map(|(i, name)| {
+        Spans::from(vec![Span::raw(tab_title(theme, name, i == app.selected_series_tab))])
     }).collect();
 
     let series_tabs_widget = Tabs::new(series_tabs)
-        .block(Block::default().borders(Borders::BOTTOM).title("Series Tabs"))
-        .style(Style::default().bg(Color::Black).fg(Color::White))
-        .highlight_style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::BOTTOM).title(app.locale.tr("Series Tabs")).style(theme.text()))
+        .style(theme.tabs())
+        .highlight_style(theme.tab_highlight())
         .divider(Span::raw(" | "))
         .select(app.selected_series_tab);
 
     f.render_widget(series_tabs_widget, area);
 }
 
-fn draw_episodes_list<B: Backend>(f: &mut Frame<B>, app: &mut App, series_index: usize, area: tui::layout::Rect) {
+fn draw_episodes_list<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, series_index: usize, area: Rect) {
     if let Some(series) = app.guide.get(series_index) {
-        let items: Vec<_> = series.episodes.iter()
-            .map(|ep| ListItem::new(format!(
-                "{}: {}",
-                ep.episode_number,
-                ep.title
-            )))
+        let items: Vec<_> = app.sorted_episodes(series_index).iter()
+            .map(|&episode_index| {
+                let ep = &series.episodes[episode_index];
+                let key = episode_key(&series.series, ep.episode_number);
+                ListItem::new(format!(
+                    "{}{} {}: {}{}",
+                    watched_mark(app, theme, &key),
+                    favorite_mark(app, theme, &key),
+                    ep.episode_number,
+                    ep.display_title(app.title_language),
+                    rating_suffix(app, &key),
+                ))
+            })
             .collect();
 
-        let sort_method = match app.episode_sort_method {
-            EpisodeSortMethod::EpisodeNumber => "Ep#",
-            EpisodeSortMethod::Title => "Title",
-            EpisodeSortMethod::ReleaseDate => "Date",
-        };
-        let sort_order = match app.episode_sort_order {
-            SortOrder::Ascending => "↑",
-            SortOrder::Descending => "↓",
-        };
-        let sort_info = format!("[{} {}]", sort_method, sort_order);
+        let sort_info = format!("[{}]", episode_sort_label(app, theme));
 
         let title = Spans::from(vec![
-            Span::styled("Episodes ", Style::default().fg(Color::LightCyan)),
-            Span::styled(sort_info, Style::default().fg(Color::LightYellow)),
+            Span::styled(app.locale.tr("Episodes "), theme.accent()),
+            Span::styled(sort_info, theme.sort_info()),
         ]);
 
         let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(title))
-            .highlight_style(Style::default().bg(Color::Yellow));
+            .block(Block::default().borders(Borders::ALL).title(title).border_style(theme.border()))
+            .highlight_style(theme.highlight())
+            .highlight_symbol(theme.highlight_symbol());
+        app.list_height = area.height.saturating_sub(2) as usize;
         f.render_stateful_widget(list, area, &mut app.list_state);
     }
 }
 
-fn draw_episode_details<B: Backend>(f: &mut Frame<B>, app: &App, series_index: usize, episode_index: usize, area: tui::layout::Rect) {
+fn draw_episode_details<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, series_index: usize, episode_index: usize, area: Rect) {
     if let Some(series) = app.guide.get(series_index) {
         if let Some(episode) = series.episodes.get(episode_index) {
             let block = Block::default()
                 .borders(Borders::ALL)
                 .title(Span::styled(
-                    format!(" Episode Details: {} ", episode.title),
-                    Style::default().add_modifier(Modifier::BOLD)
+                    app.locale.fill(" Episode Details: {} ", &[&episode.display_title(app.title_language)]),
+                    theme.accent().add_modifier(Modifier::BOLD)
                 ))
-                .border_style(Style::default().fg(Color::Cyan));
+                .border_style(theme.border());
 
-            let details = vec![
+            let mut details = vec![
                 Spans::from(vec![
-                    Span::styled("Episode Number: ", Style::default().fg(Color::Yellow)),
+                    Span::styled(app.locale.tr("Episode Number: "), theme.label()),
                     Span::raw(episode.episode_number.to_string()),
                 ]),
+            ];
+            details.extend(release_lines(app, theme, &episode.release_date, &episode.releases));
+            details.extend(vec![
                 Spans::from(vec![
-                    Span::styled("Release Date: ", Style::default().fg(Color::Yellow)),
-                    Span::raw(&episode.release_date),
-                ]),
-                Spans::from(vec![
-                    Span::styled("Duration: ", Style::default().fg(Color::Yellow)),
+                    Span::styled(app.locale.tr("Duration: "), theme.label()),
                     Span::raw(&episode.duration),
                 ]),
                 Spans::from(vec![
-                    Span::styled("Saga: ", Style::default().fg(Color::Yellow)),
+                    Span::styled(app.locale.tr("Saga: "), theme.label()),
                     Span::raw(&episode.saga),
                 ]),
+            ]);
+            details.extend(title_lines(app, theme, &episode.title, &episode.title_japanese, &episode.title_romaji, &episode.alternate_titles));
+            details.extend(vec![
                 Spans::from(""),
                 Spans::from(vec![
-                    Span::styled("Description: ", Style::default().fg(Color::Yellow)),
+                    Span::styled(app.locale.tr("Description: "), theme.label()),
                 ]),
                 Spans::from(Span::raw(&episode.description)),
-            ];
+            ]);
+            let related = related_movies(&app.movies, series, &episode.saga, Some(episode.episode_number));
+            details.extend(related_movie_lines(app, theme, &related));
+            details.extend(personal_lines(app, theme, &episode_key(&series.series, episode.episode_number)));
 
             let paragraph = Paragraph::new(details)
                 .block(block)
@@ -203,97 +835,184 @@ fn draw_episode_details<B: Backend>(f: &mut Frame<B>, app: &App, series_index: u
     }
 }
 
-fn draw_movies_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layout::Rect) {
+fn draw_saga_details<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, series_index: usize, saga: &str, area: Rect) {
+    let series = match app.guide.get(series_index) {
+        Some(series) => series,
+        None => return,
+    };
+    let mut episodes: Vec<_> = series.episodes.iter().filter(|ep| ep.saga == saga).collect();
+    episodes.sort_by_key(|ep| ep.episode_number);
+    let (first, last) = match (episodes.first(), episodes.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return,
+    };
+
+    let runtime: u32 = episodes.iter().filter_map(|ep| parse_duration_minutes(&ep.duration)).sum();
+    let watched = episodes.iter()
+        .filter(|ep| app.user_data.is_watched(&episode_key(&series.series, ep.episode_number)))
+        .count();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            app.locale.fill(" Saga: {} ", &[&saga]),
+            theme.accent().add_modifier(Modifier::BOLD)
+        ))
+        .border_style(theme.border());
+
+    let mut details = vec![
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Series: "), theme.label()),
+            Span::raw(&series.series),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Episodes: "), theme.label()),
+            Span::raw(app.locale.fill("{}-{} ({} episodes)", &[&first.episode_number, &last.episode_number, &episodes.len()])),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Aired: "), theme.label()),
+            Span::raw(format!("{} - {}", first.release_date_in(&app.region), last.release_date_in(&app.region))),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Runtime: "), theme.label()),
+            Span::raw(format_runtime(runtime)),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Watched: "), theme.label()),
+            Span::raw(format!("{}/{}", watched, episodes.len())),
+        ]),
+    ];
+    details.extend(related_movie_lines(app, theme, &related_movies(&app.movies, series, saga, None)));
+    details.push(Spans::from(""));
+    for ep in &episodes {
+        details.push(Spans::from(format!(
+            "{} {}: {}",
+            watched_mark(app, theme, &episode_key(&series.series, ep.episode_number)),
+            ep.episode_number,
+            ep.display_title(app.title_language)
+        )));
+    }
+
+    let paragraph = Paragraph::new(details)
+        .block(block)
+        .wrap(tui::widgets::Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+fn draw_movies_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
     match app.app_mode {
         AppMode::MoviesList => {
-            draw_movies_list(f, app, area);
+            match split_preview_area(app, area) {
+                Some((list_area, preview_area)) => {
+                    draw_movies_list(f, app, theme, list_area);
+                    if let Some(movie_index) = app.selected_movie() {
+                        draw_movie_details(f, app, theme, movie_index, preview_area);
+                    }
+                }
+                None => draw_movies_list(f, app, theme, area),
+            }
         }
         AppMode::MovieDetails(movie_index) => {
-            draw_movie_details(f, app, movie_index, area);
+            draw_movie_details(f, app, theme, movie_index, area);
         }
         _ => {}
     }
 }
 
-fn draw_movies_list<B: Backend>(f: &mut Frame<B>, app: &mut App, area: tui::layout::Rect) {
-    let movie_items: Vec<_> = app.movies.iter()
-        .map(|movie| ListItem::new(format!(
-            "{}: {} ",
-            movie.number,
-            movie.title,
-        )))
+fn draw_movies_list<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
+    let visible = app.visible_movies();
+    let movie_items: Vec<_> = visible.iter()
+        .map(|&movie_index| {
+            let movie = &app.movies[movie_index];
+            let key = movie_key(movie.number);
+            ListItem::new(format!(
+                "{}{} {}: {}{}",
+                watched_mark(app, theme, &key),
+                favorite_mark(app, theme, &key),
+                movie.number,
+                movie.display_title(app.title_language),
+                rating_suffix(app, &key),
+            ))
+        })
         .collect();
     
-    let sort_method = match app.movie_sort_method {
-        MovieSortMethod::Number => "Num",
-        MovieSortMethod::Title => "Title",
-        MovieSortMethod::ReleaseDate => "Date",
-    };
-    let sort_order = match app.movie_sort_order {
-        SortOrder::Ascending => "↑",
-        SortOrder::Descending => "↓",
-    };
-    let sort_info = format!("[{} {}]", sort_method, sort_order);
+    let sort_info = format!("[{}]", movie_sort_label(app, theme));
 
-    let title = Spans::from(vec![
-        Span::styled("Movies ", Style::default().fg(Color::Magenta)),
-        Span::styled(sort_info, Style::default().fg(Color::Yellow)),
-    ]);
+    let mut title = vec![
+        Span::styled(app.locale.tr("Movies "), theme.accent()),
+        Span::styled(sort_info, theme.sort_info()),
+    ];
+    if !app.movie_filter.is_empty() {
+        title.push(Span::styled(
+            app.locale.fill(" {} of {}: {}", &[&visible.len(), &app.movies.len(), &app.movie_filter.describe()]),
+            theme.accent(),
+        ));
+    }
+    let title = Spans::from(title);
 
     let movies_list = List::new(movie_items)
-        .block(Block::default().borders(Borders::ALL).title(title).style(Style::default().fg(Color::White)))
-        .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
+        .block(Block::default().borders(Borders::ALL).title(title).border_style(theme.border()))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol());
 
+    app.list_height = area.height.saturating_sub(2) as usize;
     f.render_stateful_widget(movies_list, area, &mut app.list_state);
 }
 
-fn draw_movie_details<B: Backend>(f: &mut Frame<B>, app: &App, movie_index: usize, area: tui::layout::Rect) {
+fn draw_movie_details<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, movie_index: usize, area: Rect) {
     if let Some(movie) = app.movies.get(movie_index) {
         let block = Block::default()
             .borders(Borders::ALL)
             .title(Span::styled(
-                format!(" Movie Details: {} ", movie.title),
-                Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)
+                app.locale.fill(" Movie Details: {} ", &[&movie.display_title(app.title_language)]),
+                theme.accent().add_modifier(Modifier::BOLD)
             ))
-            .border_style(Style::default().fg(Color::Gray));
+            .border_style(theme.border());
 
-        let details = vec![
+        let mut details = vec![
             Spans::from(vec![
-                Span::styled("Number: ", Style::default().fg(Color::Yellow)),
+                Span::styled(app.locale.tr("Number: "), theme.label()),
                 Span::raw(movie.number.to_string()),
             ]),
+        ];
+        details.extend(release_lines(app, theme, &movie.release_date, &movie.releases));
+        details.extend(vec![
             Spans::from(vec![
-                Span::styled("Release Date: ", Style::default().fg(Color::Yellow)),
-                Span::raw(&movie.release_date),
-            ]),
-            Spans::from(vec![
-                Span::styled("Runtime: ", Style::default().fg(Color::Yellow)),
+                Span::styled(app.locale.tr("Runtime: "), theme.label()),
                 Span::raw(&movie.runtime),
             ]),
             Spans::from(vec![
-                Span::styled("Director: ", Style::default().fg(Color::Yellow)),
+                Span::styled(app.locale.tr("Director: "), theme.label()),
                 Span::raw(&movie.director),
             ]),
             Spans::from(vec![
-                Span::styled("Genres: ", Style::default().fg(Color::Yellow)),
+                Span::styled(app.locale.tr("Genres: "), theme.label()),
                 Span::raw(movie.genres.join(", ")),
             ]),
+            Spans::from(vec![
+                Span::styled(app.locale.tr("Takes Place: "), theme.label()),
+                Span::raw(movie_placement(app, movie)),
+            ]),
+        ]);
+        details.extend(title_lines(app, theme, &movie.title, &movie.title_japanese, &movie.title_romaji, &movie.alternate_titles));
+        details.extend(vec![
             Spans::from(""),
             Spans::from(vec![
-                Span::styled("Description: ", Style::default().fg(Color::Yellow)),
+                Span::styled(app.locale.tr("Description: "), theme.label()),
             ]),
             Spans::from(Span::raw(&movie.description)),
             Spans::from(""),
             Spans::from(vec![
-                Span::styled("Trivia: ", Style::default().fg(Color::Yellow)),
+                Span::styled(app.locale.tr("Trivia: "), theme.label()),
             ]),
             Spans::from(Span::raw(&movie.trivia)),
             Spans::from(""),
             Spans::from(vec![
-                Span::styled("Plot Keywords: ", Style::default().fg(Color::Yellow)),
+                Span::styled(app.locale.tr("Plot Keywords: "), theme.label()),
                 Span::raw(movie.plot_keywords.join(", ")),
             ]),
-        ];
+        ]);
+        details.extend(personal_lines(app, theme, &movie_key(movie.number)));
 
         let paragraph = Paragraph::new(details)
             .block(block)
@@ -302,18 +1021,288 @@ fn draw_movie_details<B: Backend>(f: &mut Frame<B>, app: &App, movie_index: usiz
     }
 }
 
-fn draw_characters_tab<B: Backend>(f: &mut Frame<B>, app: &App, area: tui::layout::Rect) {
-    let characters_text = match app.app_mode {
-        AppMode::Characters => "Characters details go here.",
-        _ => "",
+fn draw_characters_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
+    match app.app_mode {
+        AppMode::Characters => {
+            match split_preview_area(app, area) {
+                Some((list_area, preview_area)) => {
+                    draw_characters_list(f, app, theme, list_area);
+                    if let Some(character_index) = app.selected_character() {
+                        draw_character_details(f, app, theme, character_index, preview_area);
+                    }
+                }
+                None => draw_characters_list(f, app, theme, area),
+            }
+        }
+        AppMode::CharacterDetails(character_index) => {
+            draw_character_details(f, app, theme, character_index, area);
+        }
+        AppMode::FamilyTree(character_index) => {
+            draw_family_tree(f, app, theme, character_index, area);
+        }
+        AppMode::CharacterComparison(first, second) => {
+            draw_character_comparison(f, app, theme, first, second, area);
+        }
+        _ => {}
+    }
+}
+
+fn draw_characters_list<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
+    let visible = app.visible_characters();
+    let items: Vec<_> = visible.iter()
+        .map(|&character_index| {
+            let character = &app.characters[character_index];
+            let mut spans = vec![Span::raw(format!(
+                "{} {} ({})",
+                favorite_mark(app, theme, &character_key(&character.name)),
+                character.name,
+                character.race,
+            ))];
+            if app.compare_with == Some(character_index) {
+                spans.push(Span::styled(app.locale.tr("  [comparing]"), theme.tag()));
+            }
+            ListItem::new(Spans::from(spans))
+        })
+        .collect();
+
+    let title = if app.character_filter.is_empty() {
+        app.locale.tr("Characters").to_string()
+    } else {
+        app.locale.fill("Characters ({} of {}: {})", &[&visible.len(), &app.characters.len(), &app.character_filter.describe()])
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(title, theme.accent())).border_style(theme.border()))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol());
+
+    app.list_height = area.height.saturating_sub(2) as usize;
+    f.render_stateful_widget(list, area, &mut app.list_state);
+}
+
+fn draw_character_details<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, character_index: usize, area: Rect) {
+    let character = match app.characters.get(character_index) {
+        Some(character) => character,
+        None => return,
     };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            app.locale.fill(" Character Details: {} ", &[&character.name]),
+            theme.accent().add_modifier(Modifier::BOLD)
+        ))
+        .border_style(theme.border());
+
+    let mut details = vec![
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Aliases: "), theme.label()),
+            Span::raw(character.aliases.join(", ")),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Race: "), theme.label()),
+            Span::raw(&character.race),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Series: "), theme.label()),
+            Span::raw(character.series.join(", ")),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Occupation: "), theme.label()),
+            Span::raw(&character.occupation),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Family: "), theme.label()),
+            Span::raw(character.family.join(", ")),
+        ]),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Powers: "), theme.label()),
+            Span::raw(character.powers.join(", ")),
+        ]),
+        Spans::from(""),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Description: "), theme.label()),
+        ]),
+        Spans::from(Span::raw(&character.description)),
+        Spans::from(""),
+        Spans::from(vec![
+            Span::styled(app.locale.tr("Key Events: "), theme.label()),
+        ]),
+    ];
+    for event in &character.key_events {
+        details.push(Spans::from(format!("- {}", event)));
+    }
+    details.extend(personal_lines(app, theme, &character_key(&character.name)));
 
-    let paragraph = Paragraph::new(characters_text)
-        .block(Block::default().borders(Borders::ALL).title("Characters"));
+    let paragraph = Paragraph::new(details)
+        .block(block)
+        .wrap(tui::widgets::Wrap { trim: true });
     f.render_widget(paragraph, area);
 }
 
-fn draw_help_screen<B: Backend>(f: &mut Frame<B>, area: tui::layout::Rect) {
+/// Lines up the fields of two characters, marking what they share.
+fn draw_character_comparison<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, first: usize, second: usize, area: Rect) {
+    let (a, b) = match (app.characters.get(first), app.characters.get(second)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return,
+    };
+    let shared_powers = a.powers.iter().filter(|power| b.powers.contains(power)).count();
+
+    let shared_mark = theme.symbol("◆ ", "= ");
+    let values = |values: &[String], other: &[String]| -> Text<'static> {
+        let lines: Vec<Spans> = values.iter()
+            .map(|value| {
+                if other.contains(value) {
+                    Spans::from(Span::styled(format!("{}{}", shared_mark, value), theme.accent().add_modifier(Modifier::BOLD)))
+                } else {
+                    Spans::from(Span::raw(format!("  {}", value)))
+                }
+            })
+            .collect();
+        Text::from(lines)
+    };
+    let single = |value: &str, other: &str| {
+        if value == other {
+            Text::styled(format!("{}{}", shared_mark, value), theme.accent().add_modifier(Modifier::BOLD))
+        } else {
+            Text::from(format!("  {}", value))
+        }
+    };
+    let events = |events: &[String]| Text::from(events.iter().map(|event| Spans::from(format!("  - {}", event))).collect::<Vec<_>>());
+
+    let fields: Vec<(&str, Text, Text)> = vec![
+        (app.locale.tr("Race"), single(&a.race, &b.race), single(&b.race, &a.race)),
+        (app.locale.tr("Series"), values(&a.series, &b.series), values(&b.series, &a.series)),
+        (app.locale.tr("Occupation"), single(&a.occupation, &b.occupation), single(&b.occupation, &a.occupation)),
+        (app.locale.tr("Powers"), values(&a.powers, &b.powers), values(&b.powers, &a.powers)),
+        (app.locale.tr("Key Events"), events(&a.key_events), events(&b.key_events)),
+    ];
+    let rows: Vec<Row> = fields.into_iter()
+        .map(|(label, left, right)| {
+            // One blank line between fields
+            let height = left.height().max(right.height()).max(1) as u16 + 1;
+            Row::new(vec![Cell::from(Span::styled(label, theme.label())), Cell::from(left), Cell::from(right)]).height(height)
+        })
+        .collect();
+
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from(Span::styled(format!("  {}", a.name), theme.heading().add_modifier(Modifier::BOLD))),
+        Cell::from(Span::styled(format!("  {}", b.name), theme.heading().add_modifier(Modifier::BOLD))),
+    ]).height(2);
+    let title = app.locale.fill(" {} vs {}: {} shared powers ", &[&a.name, &b.name, &shared_powers]);
+    let table = Table::new(rows)
+        .header(header)
+        .style(theme.text())
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(title, theme.accent().add_modifier(Modifier::BOLD))).border_style(theme.border()))
+        .widths(&[Constraint::Length(12), Constraint::Percentage(44), Constraint::Percentage(44)])
+        .column_spacing(2);
+    f.render_widget(table, area);
+}
+
+/// Draws the generations of a character's family as centered rows, oldest
+/// first, with the character itself between its siblings and spouses.
+fn draw_family_tree<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, character_index: usize, area: Rect) {
+    let character = match app.characters.get(character_index) {
+        Some(character) => character,
+        None => return,
+    };
+    let relatives = relatives(&app.characters, character_index);
+    let rows = tree_rows(&relatives);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(
+            app.locale.fill(" Family Tree: {} ", &[&character.name]),
+            theme.accent().add_modifier(Modifier::BOLD)
+        ))
+        .border_style(theme.border());
+    let inner = block.inner(area);
+
+    const LABEL_WIDTH: usize = 15;
+    let tree_width = (inner.width as usize).saturating_sub(LABEL_WIDTH);
+    let centered = |label: &str, spans: Vec<Span<'static>>| {
+        let width: usize = spans.iter().map(|span| span.width()).sum();
+        let mut line = vec![
+            Span::styled(format!("{:<width$}", label, width = LABEL_WIDTH), theme.label()),
+            Span::raw(" ".repeat(tree_width.saturating_sub(width) / 2)),
+        ];
+        line.extend(spans);
+        Spans::from(line)
+    };
+
+    let mut text = vec![Spans::from("")];
+    for (row_index, (generation, entries)) in rows.iter().enumerate() {
+        if row_index > 0 {
+            if *generation == Generation::Other {
+                text.push(Spans::from(""));
+            } else {
+                text.push(centered("", vec![Span::styled("│", theme.border())]));
+            }
+        }
+        let mut spans = Vec::new();
+        for (column, entry) in entries.iter().enumerate() {
+            if column > 0 {
+                spans.push(Span::raw("   "));
+            }
+            match entry.and_then(|index| relatives.get(index).map(|relative| (index, relative))) {
+                Some((index, relative)) => {
+                    let selected = app.list_state.selected() == Some(index);
+                    let style = if selected {
+                        theme.highlight()
+                    } else if relative.character.is_some() {
+                        theme.key()
+                    } else {
+                        theme.text()
+                    };
+                    let marker = if selected { theme.highlight_symbol() } else { "" };
+                    spans.push(Span::styled(format!("{}{} ({})", marker, relative.name, relative.label), style));
+                }
+                None => spans.push(Span::styled(format!("[{}]", character.name), theme.heading().add_modifier(Modifier::BOLD))),
+            }
+        }
+        text.push(centered(app.locale.tr(generation.title()), spans));
+    }
+    if relatives.is_empty() {
+        text.push(Spans::from(""));
+        text.push(Spans::from(Span::styled(app.locale.tr("No known family"), theme.label())));
+    }
+
+    f.render_widget(Paragraph::new(text).style(theme.text()).block(block), area);
+}
+
+fn draw_favorites_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, theme: &Theme, area: Rect) {
+    let favorites = app.favorites();
+    let items: Vec<ListItem> = favorites.iter()
+        .map(|favorite| {
+            let kind = match favorite.target {
+                SearchResultType::Episode(_, _) => "Episode",
+                SearchResultType::Movie(_) => "Movie",
+                SearchResultType::Character(_) => "Character",
+            };
+            let mut spans = vec![
+                Span::styled(format!("[{}] ", app.locale.tr(kind)), theme.tag()),
+                Span::raw(favorite.title.clone()),
+            ];
+            if !favorite.note.is_empty() {
+                spans.push(Span::styled(format!("  {}", favorite.note), theme.label()));
+            }
+            ListItem::new(Spans::from(spans))
+        })
+        .collect();
+
+    let title = if favorites.is_empty() {
+        app.locale.fill("Favorites (press {} on an episode, movie or character to add one)", &[&app.keymap.describe(Action::ToggleFavorite)])
+    } else {
+        app.locale.tr("Favorites").to_string()
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(title, theme.accent())).border_style(theme.border()))
+        .highlight_style(theme.highlight())
+        .highlight_symbol(theme.highlight_symbol());
+
+    app.list_height = area.height.saturating_sub(2) as usize;
+    f.render_stateful_widget(list, area, &mut app.list_state);
+}
+
+fn draw_help_screen<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
     let help_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -322,45 +1311,36 @@ fn draw_help_screen<B: Backend>(f: &mut Frame<B>, area: tui::layout::Rect) {
         ].as_ref())
         .split(area);
 
-    let title = Paragraph::new("Help")
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+    let title = Paragraph::new(app.locale.tr("Help"))
+        .style(theme.accent().add_modifier(Modifier::BOLD))
         .alignment(tui::layout::Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
+        .block(Block::default().borders(Borders::ALL).border_style(theme.border()));
 
     f.render_widget(title, help_layout[0]);
 
-    let help_items = vec![
-        ("Navigation", vec![
-            ("Tab", "Switch between main tabs"),
-            ("Left/Right", "Navigate series tabs (in Episodes tab)"),
-            ("Up/Down", "Navigate lists"),
-            ("Enter", "View details of selected item"),
-            ("Esc", "Go back / Exit search"),
-        ]),
-        ("Actions", vec![
-            ("Q/q", "Quit the application"),
-            ("H/h", "Toggle this help screen"),
-            ("S/s", "Enter search mode"),
-        ]),
-        ("Sorting", vec![
-            ("M/m", "Change sort method"),
-            ("O/o", "Toggle sort order"),
-        ]),
-    ];
+    // Group the actions by section, keeping the order in which sections
+    // first appear, so the help always reflects the active keymap
+    let mut help_items: Vec<(&str, Vec<Action>)> = Vec::new();
+    for &action in Action::ALL.iter().filter(|&&action| !app.keymap.keys_for(action).is_empty()) {
+        match help_items.iter_mut().find(|(section, _)| *section == action.section()) {
+            Some((_, actions)) => actions.push(action),
+            None => help_items.push((action.section(), vec![action])),
+        }
+    }
 
     let mut text = Vec::new();
 
-    for (section, items) in help_items {
+    for (section, actions) in help_items {
         text.push(Spans::from(Span::styled(
-            section,
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            app.locale.tr(section),
+            theme.heading()
         )));
         text.push(Spans::from(""));
 
-        for (key, description) in items {
+        for action in actions {
             text.push(Spans::from(vec![
-                Span::styled(format!("{:<12}", key), Style::default().fg(Color::Green)),
-                Span::raw(description),
+                Span::styled(format!("{:<16}", app.keymap.describe(action)), theme.key()),
+                Span::raw(app.locale.tr(action.description())),
             ]));
         }
 
@@ -368,8 +1348,481 @@ fn draw_help_screen<B: Backend>(f: &mut Frame<B>, area: tui::layout::Rect) {
     }
 
     let help_paragraph = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)))
+        .block(Block::default().borders(Borders::ALL).border_style(theme.border()))
         .wrap(tui::widgets::Wrap { trim: true });
 
     f.render_widget(help_paragraph, help_layout[1]);
-}
\ No newline at end of file
+}
+fn draw_quiz<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(30), Constraint::Length(28)].as_ref())
+        .split(area);
+
+    let mut text = Vec::new();
+    match &app.quiz {
+        Some(quiz) => {
+            text.push(Spans::from(Span::styled(quiz.question.text.clone(), theme.heading())));
+            text.push(Spans::from(""));
+            for (index, choice) in quiz.question.choices.iter().enumerate() {
+                // Once answered, mark the right choice and a wrong guess
+                let (mark, style) = match quiz.given {
+                    Some(_) if index == quiz.question.answer => (theme.symbol("✓", "+"), theme.accent()),
+                    Some(given) if index == given => (theme.symbol("✗", "x"), theme.label()),
+                    Some(_) => (" ", theme.text()),
+                    None if index == quiz.selected => (theme.symbol(" ", ">"), theme.highlight()),
+                    None => (" ", theme.text()),
+                };
+                text.push(Spans::from(vec![
+                    Span::raw(format!("{} ", mark)),
+                    Span::styled(format!("{}. {}", index + 1, choice), style),
+                ]));
+            }
+            if quiz.given.is_some() {
+                text.push(Spans::from(""));
+                if let Some(explanation) = &quiz.question.explanation {
+                    text.push(Spans::from(Span::raw(explanation.clone())));
+                    text.push(Spans::from(""));
+                }
+                if let Some(key) = app.keymap.keys_for(Action::Select).first() {
+                    text.push(Spans::from(Span::styled(app.locale.fill("Press {} for the next question", &[key]), theme.label())));
+                }
+            }
+        }
+        None => {
+            if let Some(key) = app.keymap.keys_for(Action::Select).first() {
+                text.push(Spans::from(Span::styled(app.locale.fill("Press {} to start a new quiz", &[key]), theme.label())));
+            }
+        }
+    }
+    let title = match &app.quiz {
+        Some(quiz) => app.locale.fill("Quiz: {} of {} right", &[&quiz.correct, &quiz.answered]),
+        None => app.locale.tr("Quiz").to_string(),
+    };
+    let question = Paragraph::new(text)
+        .style(theme.text())
+        .block(Block::default().borders(Borders::ALL).title(title).border_style(theme.border()))
+        .wrap(tui::widgets::Wrap { trim: false });
+    f.render_widget(question, chunks[0]);
+
+    let scores: Vec<ListItem> = app.user_data.quiz_scores.iter().enumerate()
+        .map(|(place, score)| {
+            ListItem::new(Spans::from(vec![
+                Span::styled(format!("{:>2}. ", place + 1), theme.label()),
+                Span::raw(format!("{:>3}/{:<3} ", score.correct, score.answered)),
+                Span::styled(score.date.clone(), theme.label()),
+            ]))
+        })
+        .collect();
+    let scores = List::new(scores)
+        .style(theme.text())
+        .block(Block::default().borders(Borders::ALL).title(app.locale.tr("High Scores")).border_style(theme.border()));
+    f.render_widget(scores, chunks[1]);
+}
+
+fn draw_stats_tab<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ].as_ref())
+        .split(area);
+
+    let pages: Vec<Spans> = STATS_PAGES.iter().enumerate()
+        .map(|(i, &page)| Spans::from(Span::raw(tab_title(theme, app.locale.tr(page), i == app.stats_page))))
+        .collect();
+    let pages = Tabs::new(pages)
+        .block(Block::default().borders(Borders::BOTTOM).title(app.locale.tr("Stats Pages")).style(theme.text()))
+        .style(theme.tabs())
+        .highlight_style(theme.tab_highlight())
+        .divider(Span::raw(" | "))
+        .select(app.stats_page);
+    f.render_widget(pages, chunks[0]);
+
+    match app.stats_page {
+        0 => draw_episodes_per_year(f, app, theme, chunks[1]),
+        1 => draw_saga_stats(f, app, theme, chunks[1]),
+        2 => {
+            let halves = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                .split(chunks[1]);
+            // Surnames keep the director bars narrow
+            let directors: Vec<(String, u64)> = movies_per_director(&app.movies).into_iter()
+                .map(|(director, count)| (director.rsplit(' ').next().unwrap_or_default().to_string(), count))
+                .collect();
+            draw_count_chart(f, theme, app.locale.tr("Movies per director"), &directors, halves[0]);
+            draw_count_chart(f, theme, app.locale.tr("Movies per genre"), &movies_per_genre(&app.movies), halves[1]);
+        }
+        3 => draw_count_chart(f, theme, app.locale.tr("Characters per race"), &characters_per_race(&app.characters), chunks[1]),
+        _ => draw_watch_progress(f, app, theme, chunks[1]),
+    }
+}
+
+/// Styles that tell the lines of a multi-series chart apart.
+fn series_styles(theme: &Theme) -> [tui::style::Style; 4] {
+    [theme.accent(), theme.label(), theme.key(), theme.tag()]
+}
+
+fn chart_marker(theme: &Theme) -> symbols::Marker {
+    if theme.plain {
+        symbols::Marker::Dot
+    } else {
+        symbols::Marker::Braille
+    }
+}
+
+fn draw_episodes_per_year<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    let per_series: Vec<(&str, Vec<(f64, f64)>)> = app.guide.iter()
+        .map(|series| {
+            let points = episodes_per_year(series).into_iter()
+                .map(|(year, count)| (year as f64, count as f64))
+                .collect();
+            (series.series.as_str(), points)
+        })
+        .collect();
+    let points = per_series.iter().flat_map(|(_, points)| points.iter());
+    let (first_year, last_year, max_count) = points.fold((f64::MAX, f64::MIN, 0.0_f64), |(min, max, top), &(year, count)| {
+        (min.min(year), max.max(year), top.max(count))
+    });
+    if max_count == 0.0 {
+        return draw_stats_message(f, theme, app.locale.tr("Episodes per year"), app.locale.tr("No release dates to chart."), area);
+    }
+
+    let styles = series_styles(theme);
+    let datasets = per_series.iter().enumerate()
+        .map(|(i, (name, points))| {
+            Dataset::default()
+                .name(*name)
+                .marker(chart_marker(theme))
+                .graph_type(GraphType::Line)
+                .style(styles[i % styles.len()])
+                .data(points)
+        })
+        .collect();
+    let mut title = vec![Span::styled(app.locale.tr("Episodes per year"), theme.accent())];
+    for (i, (name, _)) in per_series.iter().enumerate() {
+        title.push(Span::styled(format!("  {} {}", theme.symbol("■", "-"), name), styles[i % styles.len()]));
+    }
+    let middle_year = ((first_year + last_year) / 2.0).round();
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(Spans::from(title)).border_style(theme.border()))
+        .style(theme.text())
+        // The series are named in the title instead, as the legend box
+        // would hide part of the lines
+        .hidden_legend_constraints((Constraint::Ratio(0, 1), Constraint::Ratio(0, 1)))
+        .x_axis(Axis::default()
+            .bounds([first_year, last_year])
+            .labels(vec![first_year, middle_year, last_year].into_iter().map(|year| Span::styled(year.to_string(), theme.label())).collect()))
+        .y_axis(Axis::default()
+            .bounds([0.0, max_count])
+            .labels(vec![Span::raw("0"), Span::styled(max_count.to_string(), theme.label())]));
+    f.render_widget(chart, area);
+}
+
+fn draw_saga_stats<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+        .split(area);
+    let sagas = saga_stats(&app.guide);
+    let saga_name = |saga: &str| -> String {
+        // Drop the "(1986–87)" years suffix
+        let name = saga.rsplit_once(" (").map_or(saga, |(name, _)| name);
+        name.chars().take(28).collect()
+    };
+
+    // Runtime per saga as horizontal bars, grouped by series
+    let longest = sagas.iter().map(|saga| saga.minutes).max().unwrap_or(0).max(1);
+    let bar_width = (panes[0].width as usize).saturating_sub(2 + 29 + 10).max(1);
+    let mut lines = Vec::new();
+    let mut current_series = "";
+    for saga in &sagas {
+        if saga.series != current_series {
+            if !current_series.is_empty() {
+                lines.push(Spans::from(""));
+            }
+            current_series = &saga.series;
+            lines.push(Spans::from(Span::styled(current_series, theme.heading())));
+        }
+        let bar = theme.symbol("█", "#").repeat((saga.minutes as usize * bar_width).div_ceil(longest as usize));
+        lines.push(Spans::from(vec![
+            Span::styled(format!("{:<29}", saga_name(&saga.saga)), theme.label()),
+            Span::styled(bar, theme.accent()),
+            Span::raw(format!(" {}", format_runtime(saga.minutes))),
+        ]));
+    }
+    let bars = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(app.locale.tr("Runtime per saga"), theme.accent())).border_style(theme.border()))
+        .style(theme.text())
+        .scroll((app.stats_scroll, 0));
+    f.render_widget(bars, panes[0]);
+
+    // Longest and shortest sagas by runtime
+    let mut by_runtime: Vec<&SagaStats> = sagas.iter().collect();
+    by_runtime.sort_by_key(|saga| std::cmp::Reverse(saga.minutes));
+    let ranking = |sagas: &mut dyn Iterator<Item = &&SagaStats>| -> Vec<Spans> {
+        sagas.map(|saga| Spans::from(vec![
+            Span::raw(format!("{:<29}", saga_name(&saga.saga))),
+            Span::styled(app.locale.fill("{} eps, {}", &[&saga.episodes, &format_runtime(saga.minutes)]), theme.label()),
+        ])).collect()
+    };
+    let mut lines = vec![Spans::from(Span::styled(app.locale.tr("Longest"), theme.heading()))];
+    lines.extend(ranking(&mut by_runtime.iter().take(5)));
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(Span::styled(app.locale.tr("Shortest"), theme.heading())));
+    lines.extend(ranking(&mut by_runtime.iter().rev().take(5)));
+    let ranking = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(app.locale.tr("Longest and shortest sagas"), theme.accent())).border_style(theme.border()))
+        .style(theme.text());
+    f.render_widget(ranking, panes[1]);
+}
+
+/// A bar chart of how many items share each value, e.g. movies per genre.
+fn draw_count_chart<B: Backend>(f: &mut Frame<B>, theme: &Theme, title: &str, counts: &[(String, u64)], area: Rect) {
+    let data: Vec<(&str, u64)> = counts.iter().map(|(label, count)| (label.as_str(), *count)).collect();
+    let bar_width = counts.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0).clamp(3, 12) as u16;
+    let mut chart = BarChart::default()
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(title, theme.accent())).border_style(theme.border()))
+        .data(&data)
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .bar_style(theme.accent())
+        .value_style(theme.highlight())
+        .label_style(theme.label());
+    if theme.plain {
+        chart = chart.bar_set(symbols::bar::Set {
+            full: "#",
+            seven_eighths: "#",
+            three_quarters: "#",
+            five_eighths: "#",
+            half: "#",
+            three_eighths: "#",
+            one_quarter: "#",
+            one_eighth: "#",
+            empty: " ",
+        });
+    }
+    f.render_widget(chart, area);
+}
+
+fn draw_watch_progress<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    let gauges_height = app.guide.len() as u16 + 3;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(gauges_height)].as_ref())
+        .split(area);
+
+    let progress = watch_progress(&app.user_data);
+    match (progress.first(), progress.last()) {
+        (Some(&(first_day, _)), Some(&(last_day, total))) => {
+            let points: Vec<(f64, f64)> = progress.iter()
+                .map(|(day, count)| ((*day - first_day).num_days() as f64, *count as f64))
+                .collect();
+            let days = ((last_day - first_day).num_days() as f64).max(1.0);
+            let dataset = Dataset::default()
+                .name(app.locale.tr("watched"))
+                .marker(chart_marker(theme))
+                .graph_type(GraphType::Line)
+                .style(theme.accent())
+                .data(&points);
+            let chart = Chart::new(vec![dataset])
+                .block(Block::default().borders(Borders::ALL).title(Span::styled(app.locale.tr("Watched over time"), theme.accent())).border_style(theme.border()))
+                .style(theme.text())
+                .x_axis(Axis::default()
+                    .bounds([0.0, days])
+                    .labels(vec![
+                        Span::styled(first_day.to_string(), theme.label()),
+                        Span::styled(last_day.to_string(), theme.label()),
+                    ]))
+                .y_axis(Axis::default()
+                    .bounds([0.0, total as f64])
+                    .labels(vec![Span::raw("0"), Span::styled(total.to_string(), theme.label())]));
+            f.render_widget(chart, chunks[0]);
+        }
+        _ => draw_stats_message(
+            f,
+            theme,
+            app.locale.tr("Watched over time"),
+            &app.locale.fill("Nothing watched yet. Press {} on an episode or movie to mark it.", &[&app.keymap.describe(Action::ToggleWatched)]),
+            chunks[0],
+        ),
+    }
+
+    let block = Block::default().borders(Borders::ALL).title(Span::styled(app.locale.tr("Watched per series"), theme.accent())).border_style(theme.border());
+    let inner = block.inner(chunks[1]);
+    f.render_widget(block, chunks[1]);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); app.guide.len() + 1])
+        .split(inner);
+    let watched_movies = app.movies.iter().filter(|movie| app.user_data.is_watched(&movie_key(movie.number))).count();
+    let movies_percent = (watched_movies * 100).checked_div(app.movies.len()).unwrap_or(0) as u16;
+    let gauges = app.guide.iter().enumerate()
+        .map(|(series_index, series)| (series.series.as_str(), app.series_watched_percent(series_index)))
+        .chain(std::iter::once((app.locale.tr("Movies"), movies_percent)));
+    for ((name, percent), row) in gauges.zip(rows) {
+        let gauge = Gauge::default()
+            .gauge_style(theme.highlight())
+            .percent(percent)
+            .label(format!("{} {}%", name, percent));
+        f.render_widget(gauge, row);
+    }
+}
+
+fn draw_stats_message<B: Backend>(f: &mut Frame<B>, theme: &Theme, title: &str, message: &str, area: Rect) {
+    let paragraph = Paragraph::new(message.to_string())
+        .style(theme.text())
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(title.to_string(), theme.accent())).border_style(theme.border()));
+    f.render_widget(paragraph, area);
+}
+
+/// Minutes as "3h 25m".
+fn format_runtime(minutes: u32) -> String {
+    format!("{}h {}m", minutes / 60, minutes % 60)
+}
+
+fn draw_timeline_tab<B: Backend>(f: &mut Frame<B>, app: &App, theme: &Theme, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(4),
+        ].as_ref())
+        .split(area);
+
+    let lanes = lanes(app);
+    let zoom = app.timeline.zoom;
+    let zoom_label = app.locale.tr(match zoom {
+        TimelineZoom::Years => "years",
+        TimelineZoom::Months => "months",
+    });
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(app.locale.fill("Timeline [{}]", &[&zoom_label]), theme.accent()))
+        .border_style(theme.border());
+    let inner = block.inner(chunks[0]);
+    f.render_widget(block, chunks[0]);
+
+    let (origin, selected) = match (origin(&lanes), app.timeline.selected(&lanes)) {
+        (Some(origin), Some(selected)) => (origin, selected),
+        _ => return,
+    };
+    let label_width = lanes.iter().map(|lane| lane.name.chars().count()).max().unwrap_or(0) + 1;
+    let width = (inner.width as usize).saturating_sub(label_width);
+    if width == 0 {
+        return;
+    }
+
+    // Keep the selected release in the middle of the view where possible
+    let last_date = lanes.iter().filter_map(|lane| lane.items.last()).map(|item| item.date).max().unwrap_or(origin);
+    let total = zoom.column(origin, last_date) + 1;
+    let selected_column = zoom.column(origin, selected.date);
+    let start = (selected_column - width as i64 / 2).clamp(0, (total - width as i64).max(0));
+    let date_of = |column: i64| match zoom {
+        TimelineZoom::Years => origin.checked_add_months(chrono::Months::new(column as u32)),
+        TimelineZoom::Months => origin.checked_add_signed(chrono::Duration::days(column)),
+    };
+
+    let mut lines = Vec::new();
+    let padding = " ".repeat(label_width);
+
+    // Axis labels at each new year (or month when zoomed in)
+    let mut axis: Vec<char> = vec![' '; width];
+    let mut ticks: Vec<char> = vec![' '; width];
+    for offset in 0..width {
+        let date = match date_of(start + offset as i64) {
+            Some(date) => date,
+            None => continue,
+        };
+        let label = match zoom {
+            TimelineZoom::Years if date.month() == 1 => date.format("%Y").to_string(),
+            TimelineZoom::Months if date.day() == 1 => date.format("%b %Y").to_string(),
+            _ => continue,
+        };
+        ticks[offset] = theme.symbol("╷", "|").chars().next().unwrap_or('|');
+        if axis[offset.saturating_sub(1)] == ' ' {
+            for (i, c) in label.chars().enumerate().take(width - offset) {
+                axis[offset + i] = c;
+            }
+        }
+    }
+    lines.push(Spans::from(vec![Span::raw(padding.clone()), Span::styled(axis.into_iter().collect::<String>(), theme.label())]));
+    lines.push(Spans::from(vec![Span::raw(padding.clone()), Span::styled(ticks.into_iter().collect::<String>(), theme.border())]));
+
+    for (lane_index, lane) in lanes.iter().enumerate() {
+        let label_style = if lane_index == app.timeline.lane { theme.heading() } else { theme.label() };
+        let mut points = vec![0usize; width];
+        for item in &lane.items {
+            let offset = zoom.column(origin, item.date) - start;
+            if (0..width as i64).contains(&offset) {
+                points[offset as usize] += 1;
+            }
+        }
+        let mut row = vec![Span::styled(format!("{:<width$}", lane.name, width = label_width), label_style)];
+        for (offset, &count) in points.iter().enumerate() {
+            let is_selected = lane_index == app.timeline.lane && start + offset as i64 == selected_column;
+            row.push(match (is_selected, count) {
+                (true, _) => Span::styled(theme.symbol("◆", "@"), theme.highlight()),
+                (false, 0) => Span::raw(" "),
+                (false, _) => Span::styled(theme.symbol("●", "o"), theme.accent()),
+            });
+        }
+        lines.push(Spans::from(row));
+
+        if lane.sagas.is_empty() {
+            continue;
+        }
+        // Saga spans, shaded alternately and named where there is room
+        let mut shading: Vec<Span> = vec![Span::raw(" "); width];
+        for (saga_index, saga) in lane.sagas.iter().enumerate() {
+            let (shade, style) = if saga_index % 2 == 0 {
+                (theme.symbol("░", "-"), theme.label())
+            } else {
+                (theme.symbol("▒", "="), theme.tag())
+            };
+            let first = (zoom.column(origin, saga.start) - start).max(0);
+            let last = (zoom.column(origin, saga.end) - start).min(width as i64 - 1);
+            if first > last {
+                continue;
+            }
+            let name = saga.name.rsplit_once(" (").map_or(saga.name.as_str(), |(name, _)| name);
+            let mut name = name.chars();
+            let room = (last - first + 1) as usize;
+            let show_name = room > saga.name.chars().count().min(12) + 2;
+            for (i, offset) in (first..=last).enumerate() {
+                let symbol = match name.next() {
+                    Some(c) if show_name && i + 1 < room => c.to_string(),
+                    _ => shade.to_string(),
+                };
+                shading[offset as usize] = Span::styled(symbol, style);
+            }
+        }
+        let mut row = vec![Span::raw(padding.clone())];
+        row.extend(shading);
+        lines.push(Spans::from(row));
+        lines.push(Spans::from(""));
+    }
+    f.render_widget(Paragraph::new(lines).style(theme.text()), inner);
+
+    // Details of the selected release
+    let mut info = vec![Spans::from(vec![
+        Span::styled(selected.date.format("%B %-d, %Y").to_string(), theme.label()),
+        Span::styled(if selected.regional { "" } else { app.locale.tr(" (original release)") }, theme.label()),
+        Span::raw("  "),
+        Span::raw(selected.label.as_str()),
+    ])];
+    if let SearchResultType::Episode(series_index, episode_index) = selected.target {
+        if let Some(episode) = app.guide.get(series_index).and_then(|series| series.episodes.get(episode_index)) {
+            info.push(Spans::from(vec![
+                Span::styled(app.locale.tr("Saga: "), theme.label()),
+                Span::raw(episode.saga.as_str()),
+            ]));
+        }
+    }
+    let info = Paragraph::new(info)
+        .style(theme.text())
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(app.locale.tr("Selected"), theme.accent())).border_style(theme.border()));
+    f.render_widget(info, chunks[1]);
+}
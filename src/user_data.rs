@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Per-user state, kept apart from the shared dataset in `data/`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserData {
+    /// Watched episodes and movies, keyed by `episode_key`/`movie_key`,
+    /// with the date (YYYY-MM-DD) they were marked.
+    pub watched: BTreeMap<String, String>,
+    /// Starred episodes, movies and characters, keyed like `watched` (and
+    /// `character_key`).
+    pub favorites: BTreeSet<String>,
+    /// Ratings from 1 to 10 given to episodes and movies.
+    pub ratings: BTreeMap<String, u8>,
+    /// Free-text notes on episodes, movies and characters.
+    pub notes: BTreeMap<String, String>,
+    /// Best quiz sessions, best first.
+    pub quiz_scores: Vec<QuizScore>,
+}
+
+/// The result of one quiz session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuizScore {
+    pub correct: u32,
+    pub answered: u32,
+    /// Day of the session (YYYY-MM-DD).
+    pub date: String,
+}
+
+/// Longest note that can be attached to an item, in characters. Only there
+/// to keep the user data file sane; details views wrap notes of any length.
+pub const MAX_NOTE_LENGTH: usize = 2000;
+
+/// Highest rating; ratings go from 1 to this.
+pub const MAX_RATING: u8 = 10;
+
+/// How many quiz high scores are kept.
+pub const MAX_QUIZ_SCORES: usize = 10;
+
+pub fn episode_key(series: &str, episode_number: u32) -> String {
+    format!("{}/{}", series, episode_number)
+}
+
+pub fn movie_key(number: u32) -> String {
+    format!("movie/{}", number)
+}
+
+pub fn character_key(name: &str) -> String {
+    format!("character/{}", name)
+}
+
+/// Directory holding user files, e.g. `~/.local/share/dragonball-tui`.
+pub fn user_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("dragonball-tui"))
+}
+
+pub fn user_data_path() -> Option<PathBuf> {
+    user_data_dir().map(|dir| dir.join("user_data.json"))
+}
+
+pub fn load_user_data_from_file(path: &Path) -> Result<UserData, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(UserData::default());
+    }
+
+    let file_content = fs::read_to_string(path)?;
+    let mut value: serde_json::Value = serde_json::from_str(&file_content)
+        .map_err(|e| format!("Invalid user data file {}: {}", path.display(), e))?;
+    let favorite_notes = migrate_favorites(&mut value);
+    let mut user_data: UserData = serde_json::from_value(value)
+        .map_err(|e| format!("Invalid user data file {}: {}", path.display(), e))?;
+    for (key, note) in favorite_notes {
+        user_data.notes.entry(key).or_insert(note);
+    }
+    Ok(user_data)
+}
+
+/// Files written before notes moved out of favorites keep `favorites` as a
+/// map from key to note. Turns it into the list of keys and returns the
+/// notes that were set, for `notes`.
+fn migrate_favorites(value: &mut serde_json::Value) -> BTreeMap<String, String> {
+    let favorites: BTreeMap<String, String> = match value.get("favorites").filter(|favorites| favorites.is_object()) {
+        Some(favorites) => match serde_json::from_value(favorites.clone()) {
+            Ok(favorites) => favorites,
+            Err(_) => return BTreeMap::new(),
+        },
+        None => return BTreeMap::new(),
+    };
+    value["favorites"] = favorites.keys().cloned().collect();
+    favorites.into_iter().filter(|(_, note)| !note.trim().is_empty()).collect()
+}
+
+pub fn save_user_data_to_file(user_data: &UserData, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file_content = serde_json::to_string_pretty(user_data)?;
+    fs::write(path, file_content)?;
+    Ok(())
+}
+
+impl UserData {
+    pub fn is_watched(&self, key: &str) -> bool {
+        self.watched.contains_key(key)
+    }
+
+    /// Flips the watched state of `key`, recording `today` when marking it.
+    /// Returns the new state.
+    pub fn toggle_watched(&mut self, key: String, today: &str) -> bool {
+        if self.watched.remove(&key).is_some() {
+            false
+        } else {
+            self.watched.insert(key, today.to_string());
+            true
+        }
+    }
+
+    pub fn is_favorite(&self, key: &str) -> bool {
+        self.favorites.contains(key)
+    }
+
+    /// Stars or unstars `key`. Returns the new state.
+    pub fn toggle_favorite(&mut self, key: String) -> bool {
+        if self.favorites.remove(&key) {
+            false
+        } else {
+            self.favorites.insert(key);
+            true
+        }
+    }
+
+    pub fn note(&self, key: &str) -> Option<&str> {
+        self.notes.get(key).map(String::as_str)
+    }
+
+    /// Sets the note of `key`; an empty note removes it.
+    pub fn set_note(&mut self, key: String, note: &str) {
+        let note = note.trim();
+        if note.is_empty() {
+            self.notes.remove(&key);
+        } else {
+            self.notes.insert(key, note.to_string());
+        }
+    }
+
+    pub fn rating(&self, key: &str) -> Option<u8> {
+        self.ratings.get(key).copied()
+    }
+
+    /// Sets or, with `None`, clears the rating of `key`.
+    pub fn set_rating(&mut self, key: String, rating: Option<u8>) {
+        match rating {
+            Some(rating) => self.ratings.insert(key, rating.clamp(1, MAX_RATING)),
+            None => self.ratings.remove(&key),
+        };
+    }
+
+    /// Adds a finished quiz session to the high scores, ranked by right
+    /// answers and then by fewer questions needed. Returns its place
+    /// (0 for the best) if it made the list.
+    pub fn record_quiz_score(&mut self, score: QuizScore) -> Option<usize> {
+        let place = self.quiz_scores.iter()
+            .position(|other| (score.correct, std::cmp::Reverse(score.answered)) > (other.correct, std::cmp::Reverse(other.answered)))
+            .unwrap_or(self.quiz_scores.len());
+        if place >= MAX_QUIZ_SCORES {
+            return None;
+        }
+        self.quiz_scores.insert(place, score);
+        self.quiz_scores.truncate(MAX_QUIZ_SCORES);
+        Some(place)
+    }
+}